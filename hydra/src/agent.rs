@@ -0,0 +1,292 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::CallError;
+use crate::ChildSpec;
+use crate::Dest;
+use crate::ExitReason;
+use crate::From;
+use crate::GenServer;
+use crate::GenServerOptions;
+use crate::Local;
+use crate::Pid;
+use crate::Receivable;
+
+/// A function applied to the state of an agent.
+type AgentFn<T> = Box<dyn FnOnce(&mut T) -> Box<dyn Any + Send> + Send>;
+
+/// An agent message.
+#[doc(hidden)]
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "O: Serialize", deserialize = "O: DeserializeOwned"))]
+pub enum AgentMessage<T: Send + 'static, O> {
+    Apply(Local<AgentFn<T>>),
+    ApplySuccess(Local<Box<dyn Any + Send>>),
+    Operation(O),
+    OperationSuccess(O),
+}
+
+/// Errors for [Agent] calls.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum AgentError {
+    /// A call to the [Agent] server has failed.
+    CallError(CallError),
+}
+
+/// A serializable operation that can be applied to an [Agent] on any node.
+///
+/// ## Example
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// enum CounterOperation {
+///     Increment,
+///     Value(u64),
+/// }
+///
+/// impl AgentOperation<u64> for CounterOperation {
+///     fn apply(self, state: &mut u64) -> Self {
+///         if let CounterOperation::Increment = self {
+///             *state += 1;
+///         }
+///
+///         CounterOperation::Value(*state)
+///     }
+/// }
+///
+/// let pid = Agent::<u64, CounterOperation>::start_link(|| 0, GenServerOptions::new()).await?;
+///
+/// Agent::<u64, CounterOperation>::call_operation(pid, CounterOperation::Increment, None).await?;
+/// // => CounterOperation::Value(1)
+/// ```
+pub trait AgentOperation<T>: Receivable {
+    /// Applies this operation to the state of the agent, and returns the reply.
+    fn apply(self, state: &mut T) -> Self;
+}
+
+impl<T> AgentOperation<T> for () {
+    fn apply(self, _: &mut T) -> Self {}
+}
+
+/// A process that wraps a single value, which can be read and updated by other processes.
+///
+/// Local processes use closures to access the state, while remote processes use an [AgentOperation].
+///
+/// ## Example
+/// ```ignore
+/// let pid = Agent::<Vec<String>>::start_link(Vec::new, GenServerOptions::new()).await?;
+///
+/// Agent::<Vec<String>>::update(pid, |state| state.push(String::from("hello")), None).await?;
+///
+/// Agent::<Vec<String>>::get(pid, |state| state.len(), None).await?;
+/// // => 1
+/// ```
+pub struct Agent<T: Send + 'static, O = ()> {
+    init: Arc<dyn Fn() -> T + Send + Sync>,
+    state: Option<T>,
+    _operation: PhantomData<fn() -> O>,
+}
+
+impl<T, O> Agent<T, O>
+where
+    T: Send + 'static,
+    O: AgentOperation<T>,
+{
+    /// Starts an agent process without links, with the state returned by `init`.
+    pub async fn start<F>(init: F, options: GenServerOptions) -> Result<Pid, ExitReason>
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        GenServer::start(Self::new(Arc::new(init)), options).await
+    }
+
+    /// Starts an agent process linked to the current process, with the state returned by `init`.
+    pub async fn start_link<F>(init: F, options: GenServerOptions) -> Result<Pid, ExitReason>
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        GenServer::start_link(Self::new(Arc::new(init)), options).await
+    }
+
+    /// Builds a child specification for an agent process, with the state returned by `init` each time it's started.
+    pub fn child_spec<F>(init: F, options: GenServerOptions) -> ChildSpec
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+        T: Sync,
+    {
+        let init: Arc<dyn Fn() -> T + Send + Sync> = Arc::new(init);
+
+        ChildSpec::new("Agent")
+            .start(move || GenServer::start_link(Self::new(init.clone()), options.clone()))
+    }
+
+    /// Returns the value computed by `fun` from the state of the given local agent.
+    pub async fn get<D, F, R>(agent: D, fun: F, timeout: Option<Duration>) -> Result<R, AgentError>
+    where
+        D: Into<Dest>,
+        F: FnOnce(&T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        Self::apply(agent, move |state| fun(state), timeout).await
+    }
+
+    /// Updates the state of the given local agent with `fun`, and waits for the update to complete.
+    pub async fn update<D, F>(agent: D, fun: F, timeout: Option<Duration>) -> Result<(), AgentError>
+    where
+        D: Into<Dest>,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        Self::apply(agent, fun, timeout).await
+    }
+
+    /// Updates the state of the given local agent with `fun`, and returns the value computed by `fun`.
+    pub async fn get_and_update<D, F, R>(
+        agent: D,
+        fun: F,
+        timeout: Option<Duration>,
+    ) -> Result<R, AgentError>
+    where
+        D: Into<Dest>,
+        F: FnOnce(&mut T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        Self::apply(agent, fun, timeout).await
+    }
+
+    /// Updates the state of the given local agent with `fun` without waiting for the update to complete.
+    pub fn cast<D, F>(agent: D, fun: F)
+    where
+        D: Into<Dest>,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        let fun: AgentFn<T> = Box::new(move |state| {
+            fun(state);
+
+            Box::new(())
+        });
+
+        <Self as GenServer>::cast(agent.into(), AgentMessage::Apply(Local::new(fun)));
+    }
+
+    /// Applies the given operation to the state of the given agent, which may live on any node, and returns the reply.
+    pub async fn call_operation<D: Into<Dest>>(
+        agent: D,
+        operation: O,
+        timeout: Option<Duration>,
+    ) -> Result<O, AgentError> {
+        use AgentMessage::*;
+
+        match <Self as GenServer>::call(agent, Operation(operation), timeout).await? {
+            OperationSuccess(reply) => Ok(reply),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Applies the given operation to the state of the given agent, which may live on any node, without waiting for the reply.
+    pub fn cast_operation<D: Into<Dest>>(agent: D, operation: O) {
+        <Self as GenServer>::cast(agent.into(), AgentMessage::Operation(operation));
+    }
+
+    /// Synchronously stops the agent with the given `reason`.
+    pub async fn stop<D: Into<Dest> + Send>(
+        agent: D,
+        reason: ExitReason,
+        timeout: Option<Duration>,
+    ) -> Result<(), ExitReason> {
+        <Self as GenServer>::stop(agent, reason, timeout).await
+    }
+
+    /// Constructs a new [Agent] with the given init function.
+    fn new(init: Arc<dyn Fn() -> T + Send + Sync>) -> Self {
+        Self {
+            init,
+            state: None,
+            _operation: PhantomData,
+        }
+    }
+
+    /// Applies the given function to the state of the given local agent, and returns it's result.
+    async fn apply<D, F, R>(agent: D, fun: F, timeout: Option<Duration>) -> Result<R, AgentError>
+    where
+        D: Into<Dest>,
+        F: FnOnce(&mut T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        use AgentMessage::*;
+
+        let fun: AgentFn<T> = Box::new(move |state| Box::new(fun(state)));
+
+        match <Self as GenServer>::call(agent, Apply(Local::new(fun)), timeout).await? {
+            ApplySuccess(result) => match result.into_inner().downcast::<R>() {
+                Ok(result) => Ok(*result),
+                Err(_) => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the state of the agent, which is always set once the agent has started.
+    fn state(&mut self) -> &mut T {
+        match self.state.as_mut() {
+            Some(state) => state,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<T, O> GenServer for Agent<T, O>
+where
+    T: Send + 'static,
+    O: AgentOperation<T>,
+{
+    type Message = AgentMessage<T, O>;
+
+    async fn init(&mut self) -> Result<(), ExitReason> {
+        self.state = Some((self.init)());
+
+        Ok(())
+    }
+
+    async fn handle_cast(&mut self, message: Self::Message) -> Result<(), ExitReason> {
+        use AgentMessage::*;
+
+        match message {
+            Apply(fun) => {
+                let _ = (fun.into_inner())(self.state());
+            }
+            Operation(operation) => {
+                let _ = operation.apply(self.state());
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    async fn handle_call(
+        &mut self,
+        message: Self::Message,
+        _from: From,
+    ) -> Result<Option<Self::Message>, ExitReason> {
+        use AgentMessage::*;
+
+        match message {
+            Apply(fun) => Ok(Some(ApplySuccess(Local::new((fun.into_inner())(
+                self.state(),
+            ))))),
+            Operation(operation) => Ok(Some(OperationSuccess(operation.apply(self.state())))),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl std::convert::From<CallError> for AgentError {
+    fn from(value: CallError) -> Self {
+        Self::CallError(value)
+    }
+}
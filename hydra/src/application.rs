@@ -105,7 +105,7 @@ pub trait Application: Sized + Send + 'static {
                                 }
                                 Message::System(SystemMessage::Exit(epid, ereason)) => {
                                     if epid == pid {
-                                        if ereason.is_custom() && ereason != "shutdown" {
+                                        if (ereason.is_custom() || ereason.is_panic()) && ereason != "shutdown" {
                                             #[cfg(feature = "tracing")]
                                             tracing::error!(reason = ?ereason, supervisor = ?pid, "Application supervisor has terminated");
                                         } else {
@@ -186,7 +186,7 @@ pub trait Application: Sized + Send + 'static {
             });
 
             if let Ok(Some(reason)) = rx.await {
-                if reason.is_custom() && reason != "shutdown" {
+                if (reason.is_custom() || reason.is_panic()) && reason != "shutdown" {
                     panic!("Exited: {:?}", reason);
                 }
             }
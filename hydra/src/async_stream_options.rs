@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::OnTimeout;
+
+/// Options used to configure an async stream of tasks.
+#[derive(Debug, Default, Clone)]
+pub struct AsyncStreamOptions {
+    pub(crate) max_concurrency: Option<usize>,
+    pub(crate) unordered: bool,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) on_timeout: OnTimeout,
+}
+
+impl AsyncStreamOptions {
+    /// Constructs a new instance of [AsyncStreamOptions] with the default values.
+    pub const fn new() -> Self {
+        Self {
+            max_concurrency: None,
+            unordered: false,
+            timeout: None,
+            on_timeout: OnTimeout::Exit,
+        }
+    }
+
+    /// Sets the maximum number of tasks to run at the same time. (Default logical cpus)
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+
+    /// Whether or not results are emitted in the order of the input. (Default true)
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.unordered = !ordered;
+        self
+    }
+
+    /// Sets the maximum time each task is allowed to run. (Default 5000ms)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets what happens when a task times out. (Default [OnTimeout::Exit])
+    pub fn on_timeout(mut self, on_timeout: OnTimeout) -> Self {
+        self.on_timeout = on_timeout;
+        self
+    }
+}
@@ -1,13 +1,27 @@
+use std::backtrace::Backtrace;
+use std::backtrace::BacktraceStatus;
+use std::cell::RefCell;
 use std::future::Future;
 use std::panic::catch_unwind;
 use std::panic::AssertUnwindSafe;
 use std::panic::UnwindSafe;
 use std::pin::Pin;
+use std::sync::Once;
 use std::task::Context;
 use std::task::Poll;
 
 use pin_project_lite::pin_project;
 
+use crate::ExitPanic;
+
+/// Installs the panic hook that captures the location and backtrace of a panic once.
+static PANIC_HOOK_ONCE: Once = Once::new();
+
+thread_local! {
+    /// The location and backtrace of the last panic on this thread.
+    static LAST_PANIC: RefCell<Option<ExitPanic>> = const { RefCell::new(None) };
+}
+
 pin_project! {
     /// A future that will catch panics and unwind them.
     pub struct AsyncCatchUnwind<Fut>
@@ -25,6 +39,8 @@ where
 {
     /// Constructs a new [CatchUnwind] for the given future.
     pub fn new(future: Fut) -> Self {
+        PANIC_HOOK_ONCE.call_once(panic_hook_install);
+
         Self { future }
     }
 }
@@ -33,21 +49,54 @@ impl<Fut> Future for AsyncCatchUnwind<Fut>
 where
     Fut: Future + UnwindSafe,
 {
-    type Output = Result<Fut::Output, String>;
+    type Output = Result<Fut::Output, ExitPanic>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let f = self.project().future;
 
         catch_unwind(AssertUnwindSafe(|| f.poll(cx)))
             .map_err(|x| {
-                if x.is::<String>() {
-                    return *x.downcast::<String>().unwrap();
+                let message = if x.is::<String>() {
+                    *x.downcast::<String>().unwrap()
                 } else if x.is::<&str>() {
-                    return x.downcast::<&str>().unwrap().to_string();
-                }
+                    x.downcast::<&str>().unwrap().to_string()
+                } else {
+                    "Unknown error!".to_string()
+                };
+
+                let mut panic = LAST_PANIC.take().unwrap_or_else(|| ExitPanic::new(""));
 
-                "Unknown error!".to_string()
+                panic.message = message;
+                panic
             })?
             .map(Ok)
     }
 }
+
+/// Chains a panic hook that records the location and backtrace of a panic for the process that caught it.
+fn panic_hook_install() {
+    let prev_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let file = panic_info
+            .location()
+            .map(|location| location.file().to_string());
+        let line = panic_info.location().map(|location| location.line());
+
+        let backtrace = Backtrace::capture();
+        let backtrace = if backtrace.status() == BacktraceStatus::Captured {
+            Some(backtrace.to_string())
+        } else {
+            None
+        };
+
+        LAST_PANIC.set(Some(ExitPanic {
+            message: String::new(),
+            file,
+            line,
+            backtrace,
+        }));
+
+        prev_hook(panic_info);
+    }));
+}
@@ -0,0 +1,90 @@
+use std::io::Error;
+use std::io::ErrorKind;
+
+/// The algorithm used to compress frames sent to remote nodes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Frames are never compressed.
+    #[default]
+    None,
+    /// Frames are compressed with lz4, which is very fast with a moderate ratio.
+    #[cfg(feature = "lz4")]
+    Lz4,
+    /// Frames are compressed with zstd at the given level, which has a high ratio.
+    #[cfg(feature = "zstd")]
+    Zstd(i32),
+}
+
+impl Compression {
+    /// Returns the wire id of this algorithm.
+    pub(crate) const fn id(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => 1,
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => 2,
+        }
+    }
+
+    /// Returns the wire ids of every algorithm this node can decompress.
+    pub(crate) fn supported() -> Vec<u8> {
+        vec![
+            #[cfg(feature = "lz4")]
+            Self::Lz4.id(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(0).id(),
+        ]
+    }
+
+    /// Negotiates the algorithm used to send frames, given the algorithms a remote node supports.
+    pub(crate) fn negotiate(self, supported: &[u8]) -> Self {
+        if supported.contains(&self.id()) {
+            self
+        } else {
+            Self::None
+        }
+    }
+
+    /// Compresses the given data.
+    pub(crate) fn compress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(level) => zstd::bulk::compress(data, *level),
+        }
+    }
+
+    /// Decompresses data compressed with the algorithm with the given wire id, up to `max_size` bytes.
+    pub(crate) fn decompress(id: u8, data: &[u8], max_size: usize) -> Result<Vec<u8>, Error> {
+        #[cfg(not(any(feature = "lz4", feature = "zstd")))]
+        let _ = (data, max_size);
+
+        match id {
+            #[cfg(feature = "lz4")]
+            1 => {
+                let (size, _) = lz4_flex::block::uncompressed_size(data)
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+                if size > max_size {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "Decompressed frame exceeds the maximum frame size!",
+                    ));
+                }
+
+                lz4_flex::decompress_size_prepended(data)
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            }
+            #[cfg(feature = "zstd")]
+            2 => zstd::bulk::decompress(data, max_size)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Unsupported frame compression!",
+            )),
+        }
+    }
+}
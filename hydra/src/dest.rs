@@ -3,6 +3,7 @@ use std::borrow::Cow;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::global_name_lookup;
 use crate::Node;
 use crate::Pid;
 use crate::Reference;
@@ -16,6 +17,8 @@ pub enum Dest {
     Named(Cow<'static, str>, Node),
     /// A reference to an alias.
     Alias(Reference),
+    /// A globally registered process name, resolved on whichever node the process lives.
+    Global(Cow<'static, str>),
 }
 
 /// One or more process destinations.
@@ -29,20 +32,34 @@ pub enum Dests {
 
 impl Dest {
     /// Returns `true` if the [Dest] is for a local process.
+    ///
+    /// Always `false` for [Dest::Global], which is resolved when sending, or monitoring.
     pub const fn is_local(&self) -> bool {
         match self {
             Self::Pid(pid) => pid.is_local(),
             Self::Named(_, node) => node.is_local(),
             Self::Alias(reference) => reference.is_local(),
+            Self::Global(_) => false,
         }
     }
 
     /// Returns `true` if the [Dest] is for a remote process.
+    ///
+    /// Always `false` for [Dest::Global], which is resolved when sending, or monitoring.
     pub const fn is_remote(&self) -> bool {
         match self {
             Self::Pid(pid) => pid.is_remote(),
             Self::Named(_, node) => node.is_remote(),
             Self::Alias(reference) => reference.is_remote(),
+            Self::Global(_) => false,
+        }
+    }
+
+    /// Resolves a global name to the process it's registered to, other destinations are returned as is.
+    pub(crate) fn resolve(self) -> Option<Self> {
+        match self {
+            Self::Global(name) => global_name_lookup(&name).map(Self::Pid),
+            dest => Some(dest),
         }
     }
 }
@@ -143,6 +160,16 @@ impl PartialEq<Dest> for Reference {
     }
 }
 
+impl Dests {
+    /// Returns the number of destinations.
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Dest(_) => 1,
+            Self::Dests(dests) => dests.len(),
+        }
+    }
+}
+
 impl From<Pid> for Dests {
     fn from(value: Pid) -> Self {
         Self::Dest(Dest::from(value))
@@ -0,0 +1,30 @@
+use bincode::Decode;
+use bincode::Encode;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Information about a panic that caused a process to exit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct ExitPanic {
+    /// The panic message, or `Unknown error!` if the payload was not a string.
+    pub message: String,
+    /// The file the panic occured in, if known.
+    pub file: Option<String>,
+    /// The line the panic occured on, if known.
+    pub line: Option<u32>,
+    /// The backtrace of the panic, captured when enabled with the `RUST_BACKTRACE` environment variable.
+    pub backtrace: Option<String>,
+}
+
+impl ExitPanic {
+    /// Constructs a new [ExitPanic] with the given message, and no location or backtrace.
+    pub fn new<T: Into<String>>(message: T) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            line: None,
+            backtrace: None,
+        }
+    }
+}
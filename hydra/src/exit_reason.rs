@@ -1,9 +1,12 @@
 use bincode::Decode;
 use bincode::Encode;
 
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::ExitPanic;
+
 /// Represents the reason a process exits.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub enum ExitReason {
@@ -16,6 +19,10 @@ pub enum ExitReason {
     Ignore,
     /// Custom exit reason.
     Custom(String),
+    /// Custom exit reason with a serialized payload.
+    Payload(String, Vec<u8>),
+    /// Exited due to a panic.
+    Panic(ExitPanic),
 }
 
 impl ExitReason {
@@ -34,9 +41,39 @@ impl ExitReason {
         matches!(self, ExitReason::Ignore)
     }
 
-    /// Whether or not the reason is custom.
+    /// Whether or not the reason is custom, with or without a payload.
     pub const fn is_custom(&self) -> bool {
-        matches!(self, ExitReason::Custom(_))
+        matches!(self, ExitReason::Custom(_) | ExitReason::Payload(_, _))
+    }
+
+    /// Whether or not the reason is a panic.
+    pub const fn is_panic(&self) -> bool {
+        matches!(self, ExitReason::Panic(_))
+    }
+
+    /// Constructs a custom exit reason with the given payload.
+    ///
+    /// Panics if the payload fails to serialize.
+    pub fn with_payload<T: Into<String>, P: Serialize>(reason: T, payload: &P) -> Self {
+        let payload = rmp_serde::to_vec_named(payload).expect("Failed to serialize payload!");
+
+        Self::Payload(reason.into(), payload)
+    }
+
+    /// Returns the custom reason, if any.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Custom(reason) | Self::Payload(reason, _) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Deserializes the payload of a custom exit reason, if any.
+    pub fn payload<P: DeserializeOwned>(&self) -> Option<P> {
+        match self {
+            Self::Payload(_, payload) => rmp_serde::from_slice(payload).ok(),
+            _ => None,
+        }
     }
 }
 
@@ -52,20 +89,20 @@ impl From<&str> for ExitReason {
     }
 }
 
+impl From<ExitPanic> for ExitReason {
+    fn from(value: ExitPanic) -> Self {
+        Self::Panic(value)
+    }
+}
+
 impl PartialEq<&str> for ExitReason {
     fn eq(&self, other: &&str) -> bool {
-        match self {
-            Self::Custom(reason) => reason == other,
-            _ => false,
-        }
+        self.reason() == Some(*other)
     }
 }
 
 impl PartialEq<ExitReason> for &str {
     fn eq(&self, other: &ExitReason) -> bool {
-        match other {
-            ExitReason::Custom(reason) => self == reason,
-            _ => false,
-        }
+        other.reason() == Some(*self)
     }
 }
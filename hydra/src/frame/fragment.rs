@@ -0,0 +1,49 @@
+use std::collections::VecDeque;
+use std::io::Error;
+use std::io::ErrorKind;
+
+use bincode::Decode;
+use bincode::Encode;
+
+use crate::frame::Send;
+use crate::frame::FRAME_CONFIG;
+
+/// The maximum number of large messages being fragmented at once on a connection.
+pub const MAX_FRAGMENTED_MESSAGES: usize = 16;
+
+/// The frame used to send part of a large message, fragments with the same id are joined in order.
+#[derive(Debug, Encode, Decode)]
+pub struct Fragment {
+    pub id: u64,
+    pub last: bool,
+    pub data: Vec<u8>,
+}
+
+impl Fragment {
+    /// Constructs a new instance of [Fragment] frame.
+    pub const fn new(id: u64, last: bool, data: Vec<u8>) -> Self {
+        Self { id, last, data }
+    }
+
+    /// Splits the given [Send] frame into fragments of at most `size` bytes.
+    pub fn split(id: u64, send: Send, size: usize) -> Result<VecDeque<Fragment>, Error> {
+        let encoded = bincode::encode_to_vec(send, FRAME_CONFIG)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+
+        let mut chunks = encoded.chunks(size.max(1)).peekable();
+        let mut fragments = VecDeque::new();
+
+        while let Some(chunk) = chunks.next() {
+            fragments.push_back(Fragment::new(id, chunks.peek().is_none(), chunk.to_vec()));
+        }
+
+        Ok(fragments)
+    }
+
+    /// Joins the data of every fragment of a message back into the [Send] frame.
+    pub fn join(data: &[u8]) -> Result<Send, Error> {
+        bincode::decode_from_slice(data, FRAME_CONFIG)
+            .map(|(send, _)| send)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
@@ -9,21 +9,39 @@ use bincode::Decode;
 use bincode::Encode;
 
 use crate::node_get_cookie;
+use crate::Compression;
+use crate::MessageFormat;
 
 /// Hmac using sha256.
 type HmacSha256 = Hmac<Sha256>;
 
+/// The version of the node protocol, bumped whenever the layout of a frame changes.
+///
+/// Version 1 added:
+/// - The hidden flag of the hello, and the peers frame.
+/// - The timestamp of the ping and pong frames.
+/// - The fragment frame for large messages.
+/// - The compression bits of the frame marker, and the compressions supported in the hello.
+/// - The message format of the send frame, and the formats supported in the hello.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 /// The frame used to handshake with other nodes.
+///
+/// The protocol version always comes first, so that it's checked before anything else.
 #[derive(Debug, Encode, Decode)]
 pub struct Hello {
+    pub version: u32,
     pub name: String,
     pub broadcast_address: SocketAddr,
+    pub hidden: bool,
+    pub compression: Vec<u8>,
+    pub message_formats: Vec<u8>,
     pub challenge: Vec<u8>,
 }
 
 impl Hello {
     /// Constructs a new instance of the [Hello] frame.
-    pub fn new(name: String, broadcast_address: SocketAddr) -> Self {
+    pub fn new(name: String, broadcast_address: SocketAddr, hidden: bool) -> Self {
         let mut challenge = {
             let cookie = node_get_cookie();
             let cookie = cookie
@@ -38,8 +56,12 @@ impl Hello {
         challenge.update(broadcast_address.to_string().as_bytes());
 
         Self {
+            version: PROTOCOL_VERSION,
             name,
             broadcast_address,
+            hidden,
+            compression: Compression::supported(),
+            message_formats: MessageFormat::supported(),
             challenge: challenge.finalize().into_bytes().to_vec(),
         }
     }
@@ -1,5 +1,6 @@
 use std::io::Error;
 use std::io::ErrorKind;
+use std::sync::Arc;
 
 use bincode::Decode;
 use bincode::Encode;
@@ -17,42 +18,58 @@ use bytes::BytesMut;
 use tokio_util::codec::Decoder;
 use tokio_util::codec::Encoder;
 
+use crate::Compression;
+use crate::NodeStats;
+
 mod exit;
+mod fragment;
 mod hello;
 mod link;
 mod link_down;
 mod monitor;
 mod monitor_down;
 mod monitor_update;
+mod peers;
 mod ping;
 mod pong;
 mod send;
 
 pub use exit::*;
+pub use fragment::*;
 pub use hello::*;
 pub use link::*;
 pub use link_down::*;
 pub use monitor::*;
 pub use monitor_down::*;
 pub use monitor_update::*;
+pub use peers::*;
 pub use ping::*;
 pub use pong::*;
 pub use send::*;
 
 /// Bincode configuration for the frame codec.
-const FRAME_CONFIG: Configuration<LittleEndian, Fixint> = config::standard()
+pub(crate) const FRAME_CONFIG: Configuration<LittleEndian, Fixint> = config::standard()
     .with_fixed_int_encoding()
     .with_little_endian();
 
 /// The size of the marker.
 const MARKER_LENGTH: usize = std::mem::size_of::<u32>();
 
+/// The bits of the marker that hold the frame length, the rest hold the compression id.
+const MARKER_LENGTH_MASK: u32 = (1 << 30) - 1;
+
+/// The shift of the compression id in the marker.
+const MARKER_COMPRESSION_SHIFT: u32 = 30;
+
+/// The room allowed on top of the maximum message size for the frame headers and send targets.
+pub const MAX_FRAME_OVERHEAD: usize = 64 * 1024;
+
 /// A frame value for the codec.
 #[derive(Debug, Encode, Decode)]
 pub enum Frame {
     Hello(Hello),
-    Ping,
-    Pong,
+    Ping(Ping),
+    Pong(Pong),
     Send(Send),
     Monitor(Monitor),
     MonitorDown(MonitorDown),
@@ -60,6 +77,21 @@ pub enum Frame {
     Link(Link),
     LinkDown(LinkDown),
     Exit(Exit),
+    Peers(Peers),
+    Fragment(Fragment),
+}
+
+impl Frame {
+    /// Returns the id of the local process this frame originates from, if the frame carries it.
+    pub const fn origin(&self) -> Option<u64> {
+        match self {
+            Self::Monitor(monitor) => monitor.from_id,
+            Self::Link(link) => Some(link.from_id),
+            Self::LinkDown(link_down) => Some(link_down.from_id),
+            Self::Exit(exit) => Some(exit.from_id),
+            _ => None,
+        }
+    }
 }
 
 impl From<Hello> for Frame {
@@ -69,14 +101,14 @@ impl From<Hello> for Frame {
 }
 
 impl From<Ping> for Frame {
-    fn from(_: Ping) -> Self {
-        Self::Ping
+    fn from(value: Ping) -> Self {
+        Self::Ping(value)
     }
 }
 
 impl From<Pong> for Frame {
-    fn from(_: Pong) -> Self {
-        Self::Pong
+    fn from(value: Pong) -> Self {
+        Self::Pong(value)
     }
 }
 
@@ -122,14 +154,43 @@ impl From<Exit> for Frame {
     }
 }
 
+impl From<Peers> for Frame {
+    fn from(value: Peers) -> Self {
+        Self::Peers(value)
+    }
+}
+
+impl From<Fragment> for Frame {
+    fn from(value: Fragment) -> Self {
+        Self::Fragment(value)
+    }
+}
+
 /// The frame codec.
-#[derive(Default)]
-pub struct Codec;
+pub struct Codec {
+    stats: Arc<NodeStats>,
+    max_frame_size: usize,
+    compression: Compression,
+    compression_threshold: usize,
+}
 
 impl Codec {
-    /// Constructs a new instance of [Codec] with default settings.
-    pub const fn new() -> Self {
-        Self
+    /// Constructs a new instance of [Codec] which records traffic to the given stats, and rejects frames larger than the given message size.
+    pub fn new(stats: Arc<NodeStats>, max_message_size: usize) -> Self {
+        Self {
+            stats,
+            max_frame_size: max_message_size
+                .saturating_add(MAX_FRAME_OVERHEAD)
+                .min(MARKER_LENGTH_MASK as usize),
+            compression: Compression::None,
+            compression_threshold: 0,
+        }
+    }
+
+    /// Sets the compression used for outbound frames larger than the given threshold.
+    pub fn set_compression(&mut self, compression: Compression, threshold: usize) {
+        self.compression = compression;
+        self.compression_threshold = threshold;
     }
 }
 
@@ -141,10 +202,36 @@ impl Encoder<Frame> for Codec {
 
         dst.put_u32_le(0);
 
-        let size = bincode::encode_into_std_write(item, &mut dst.writer(), FRAME_CONFIG)
+        let mut size = bincode::encode_into_std_write(item, &mut dst.writer(), FRAME_CONFIG)
             .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
 
-        dst[marker..marker + MARKER_LENGTH].copy_from_slice(&(size as u32).to_le_bytes());
+        let mut compression = Compression::None;
+
+        if self.compression != Compression::None && size > self.compression_threshold {
+            let compressed = self.compression.compress(&dst[marker + MARKER_LENGTH..])?;
+
+            // Incompressible frames are sent as is, so the remote node doesn't pay to decompress them.
+            if compressed.len() < size {
+                dst.truncate(marker + MARKER_LENGTH);
+                dst.extend_from_slice(&compressed);
+
+                size = compressed.len();
+                compression = self.compression;
+            }
+        }
+
+        if size > MARKER_LENGTH_MASK as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Frame exceeds the maximum frame size!",
+            ));
+        }
+
+        let marker_value = size as u32 | ((compression.id() as u32) << MARKER_COMPRESSION_SHIFT);
+
+        dst[marker..marker + MARKER_LENGTH].copy_from_slice(&marker_value.to_le_bytes());
+
+        self.stats.record_out(MARKER_LENGTH + size);
 
         Ok(())
     }
@@ -163,18 +250,51 @@ impl Decoder for Codec {
 
         length_marker.copy_from_slice(&src[0..MARKER_LENGTH]);
 
-        let length = u32::from_le_bytes(length_marker) as usize;
+        let marker_value = u32::from_le_bytes(length_marker);
+
+        let length = (marker_value & MARKER_LENGTH_MASK) as usize;
+        let compression = (marker_value >> MARKER_COMPRESSION_SHIFT) as u8;
+
+        if length > self.max_frame_size {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Frame of {} bytes exceeds the maximum frame size of {} bytes!",
+                    length, self.max_frame_size
+                ),
+            ));
+        }
 
         if src.len() < MARKER_LENGTH + length {
             src.reserve(MARKER_LENGTH + length - src.len());
             return Ok(None);
         }
 
+        if compression != 0 {
+            let data = Compression::decompress(
+                compression,
+                &src[MARKER_LENGTH..MARKER_LENGTH + length],
+                self.max_frame_size,
+            )?;
+
+            let (frame, _) = bincode::decode_from_slice(&data, FRAME_CONFIG)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+            src.advance(MARKER_LENGTH + length);
+
+            self.stats.record_in(MARKER_LENGTH + length);
+
+            return Ok(Some(frame));
+        }
+
         let result = bincode::decode_from_slice(&src[4..], FRAME_CONFIG);
 
         match result {
             Ok((frame, _)) => {
                 src.advance(MARKER_LENGTH + length);
+
+                self.stats.record_in(MARKER_LENGTH + length);
+
                 Ok(Some(frame))
             }
             Err(DecodeError::UnexpectedEnd { additional }) => {
@@ -0,0 +1,17 @@
+use std::net::SocketAddr;
+
+use bincode::Decode;
+use bincode::Encode;
+
+/// The frame used to share the connected peers of a node.
+#[derive(Debug, Encode, Decode)]
+pub struct Peers {
+    pub nodes: Vec<(String, SocketAddr)>,
+}
+
+impl Peers {
+    /// Constructs a new instance of [Peers] frame.
+    pub const fn new(nodes: Vec<(String, SocketAddr)>) -> Self {
+        Self { nodes }
+    }
+}
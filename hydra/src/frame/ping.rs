@@ -1,6 +1,15 @@
 use bincode::Decode;
 use bincode::Encode;
 
-/// The frame used to keep the connection alive.
+/// The frame used to keep the connection alive, and measure the round trip time.
 #[derive(Debug, Encode, Decode)]
-pub struct Ping;
+pub struct Ping {
+    pub timestamp: u64,
+}
+
+impl Ping {
+    /// Constructs a new instance of [Ping] frame.
+    pub const fn new(timestamp: u64) -> Self {
+        Self { timestamp }
+    }
+}
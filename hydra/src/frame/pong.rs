@@ -1,6 +1,15 @@
 use bincode::Decode;
 use bincode::Encode;
 
-/// The frame used to keep the connection alive.
+/// The frame used to reply to a [super::Ping] frame, echoing it's timestamp.
 #[derive(Debug, Encode, Decode)]
-pub struct Pong;
+pub struct Pong {
+    pub timestamp: u64,
+}
+
+impl Pong {
+    /// Constructs a new instance of [Pong] frame.
+    pub const fn new(timestamp: u64) -> Self {
+        Self { timestamp }
+    }
+}
@@ -1,9 +1,11 @@
 use std::borrow::Cow;
 use std::num::NonZeroU64;
+use std::sync::Arc;
 
 use bincode::Decode;
 use bincode::Encode;
 
+use crate::MessageFormat;
 use crate::Pid;
 use crate::Reference;
 
@@ -19,38 +21,43 @@ pub enum SendTarget {
 #[derive(Debug, Encode, Decode)]
 pub struct Send {
     pub targets: Vec<SendTarget>,
-    pub message: Vec<u8>,
+    pub format: u8,
+    pub message: Arc<[u8]>,
 }
 
 impl Send {
-    /// Constructs a new [Send] frame with the given message.
-    pub fn with_message(message: Vec<u8>) -> Self {
+    /// Constructs a new [Send] frame with the given message in the given format.
+    pub fn with_message(format: MessageFormat, message: Arc<[u8]>) -> Self {
         Self {
             targets: Vec::new(),
+            format: format.id(),
             message,
         }
     }
 
     /// Constructs a new [Send] frame with the given process id.
-    pub fn with_pid(id: NonZeroU64, message: Vec<u8>) -> Self {
+    pub fn with_pid(id: NonZeroU64, format: MessageFormat, message: Arc<[u8]>) -> Self {
         Self {
             targets: vec![SendTarget::Pid(id)],
+            format: format.id(),
             message,
         }
     }
 
     /// Constructs a new [Send] frame with the given process name.
-    pub fn with_name(name: String, message: Vec<u8>) -> Self {
+    pub fn with_name(name: String, format: MessageFormat, message: Arc<[u8]>) -> Self {
         Self {
             targets: vec![SendTarget::Named(name)],
+            format: format.id(),
             message,
         }
     }
 
     /// Constructs a new [Send] frame with the given alias.
-    pub fn with_alias(alias: NonZeroU64, message: Vec<u8>) -> Self {
+    pub fn with_alias(alias: NonZeroU64, format: MessageFormat, message: Arc<[u8]>) -> Self {
         Self {
             targets: vec![SendTarget::Alias(alias)],
+            format: format.id(),
             message,
         }
     }
@@ -17,6 +17,7 @@ use crate::Pid;
 use crate::Process;
 use crate::Receivable;
 use crate::Reference;
+use crate::SpawnOptions;
 use crate::SystemMessage;
 
 /// Unique message type for a [GenServer] cast, call, and reply.
@@ -219,6 +220,11 @@ pub trait GenServer: Sized + Send + 'static {
         let server = server.into();
 
         async move {
+            // Global names are resolved once, so the monitor, and the call go to the same process.
+            let Some(server) = server.resolve() else {
+                return Err(CallError::ServerDown(ExitReason::from("noproc")));
+            };
+
             let monitor = if server.is_local() {
                 Process::monitor(server.clone())
             } else {
@@ -357,24 +363,22 @@ async fn start_gen_server<T: GenServer>(
 
     let parent: Option<Pid> = link.then(Process::current);
 
+    let mut options = options;
+    let mut spawn_options = SpawnOptions::new()
+        .link(link)
+        .flags(options.flags)
+        .label(std::any::type_name::<T>());
+
+    if let Some(name) = options.name.take() {
+        spawn_options = spawn_options.name(name);
+    }
+
     let server = async move {
         let mut gen_server = gen_server;
         let mut options = options;
 
         let parent = parent.unwrap_or(Process::current());
 
-        let registered = if let Some(name) = options.name.take() {
-            Process::register(Process::current(), name).is_ok()
-        } else {
-            true
-        };
-
-        if !registered {
-            tx.send(Err(ExitReason::from("already_started")))
-                .expect("Failed to notify parent process!");
-            return;
-        }
-
         let timeout = if let Some(duration) = options.timeout.take() {
             Process::timeout(duration, gen_server.init()).await
         } else {
@@ -453,10 +457,8 @@ async fn start_gen_server<T: GenServer>(
         }
     };
 
-    let pid = if link {
-        Process::spawn_link(server)
-    } else {
-        Process::spawn(server)
+    let Ok((pid, _)) = Process::spawn_opt(server, spawn_options) else {
+        return Err(ExitReason::from("already_started"));
     };
 
     rx.await
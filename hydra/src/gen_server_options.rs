@@ -1,10 +1,13 @@
 use std::time::Duration;
 
+use crate::ProcessFlags;
+
 /// Options used to configure a GenServer.
 #[derive(Debug, Default, Clone)]
 pub struct GenServerOptions {
     pub(crate) name: Option<String>,
     pub(crate) timeout: Option<Duration>,
+    pub(crate) flags: ProcessFlags,
 }
 
 impl GenServerOptions {
@@ -13,6 +16,7 @@ impl GenServerOptions {
         Self {
             name: None,
             timeout: None,
+            flags: ProcessFlags::empty(),
         }
     }
 
@@ -0,0 +1,13 @@
+/// Controls how a global name registered on both sides of a healed netsplit is resolved.
+///
+/// Every node picks the same winner, the process on the node with the lowest name and address.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GlobalConflict {
+    /// The winner keeps the name, and the other process is exited with the `name_conflict` reason.
+    #[default]
+    ExitLoser,
+    /// The winner keeps the name, and the other process keeps running without the name.
+    UnregisterLoser,
+    /// Neither process keeps the name, and both keep running.
+    UnregisterBoth,
+}
@@ -0,0 +1,637 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use dashmap::DashMap;
+
+use once_cell::sync::Lazy;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::node_lookup_local;
+use crate::node_lookup_remote;
+use crate::CallError;
+use crate::ChildSpec;
+use crate::ChildType;
+use crate::Dest;
+use crate::ExitReason;
+use crate::From;
+use crate::GenServer;
+use crate::GenServerOptions;
+use crate::GlobalConflict;
+use crate::Message;
+use crate::Node;
+use crate::Pid;
+use crate::Process;
+use crate::Reference;
+use crate::SystemMessage;
+
+/// The name the global name server is registered under on every node.
+const GLOBAL_NAMES_SERVER: &str = "hydra_global_names";
+
+/// The number of times a registration is retried while the name is locked by another node.
+const GLOBAL_NAMES_RETRIES: u64 = 10;
+
+/// A local view of every globally registered name.
+static GLOBAL_NAMES: Lazy<DashMap<String, Pid>> = Lazy::new(DashMap::new);
+
+/// A global names message.
+#[doc(hidden)]
+#[derive(Serialize, Deserialize)]
+pub enum GlobalNamesMessage {
+    Register(String, Pid),
+    RegisterSuccess,
+    RegisterError(GlobalNamesError),
+    Unregister(String),
+    UnregisterSuccess(Option<Pid>),
+    Discover(Pid),
+    Sync(Pid, Vec<(String, Pid)>),
+    Lock(Pid, u64, String),
+    LockReply(Pid, u64, bool),
+    Unlock(Pid, u64, String),
+    PeerRegister(Pid, u64, String, Pid),
+    PeerUnregister(String, Pid),
+}
+
+/// Errors for [GlobalNames] calls.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum GlobalNamesError {
+    /// A call to the [GlobalNames] server has failed.
+    CallError(CallError),
+    /// Only local processes can be registered.
+    NotLocal,
+    /// The name is already registered to the given process.
+    AlreadyRegistered(Pid),
+    /// The name is being registered by another node.
+    Locked,
+}
+
+/// A registration waiting for every peer to lock the name.
+struct GlobalNamesPending {
+    from: From,
+    name: String,
+    pid: Pid,
+    waiting: BTreeSet<Pid>,
+    granted: Vec<Pid>,
+    denied: bool,
+}
+
+/// Provides names that are unique across every connected node running [GlobalNames].
+///
+/// A name is locked on every connected node before it's registered, and registered processes are unregistered when they exit.
+pub struct GlobalNames {
+    conflict: GlobalConflict,
+    running: bool,
+    peers: BTreeMap<Pid, Reference>,
+    owned: BTreeMap<String, (Pid, Reference)>,
+    locks: BTreeMap<String, (Pid, u64)>,
+    pending: BTreeMap<u64, GlobalNamesPending>,
+    next_lock: u64,
+}
+
+impl GlobalNames {
+    /// Constructs a new [GlobalNames] server.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            conflict: GlobalConflict::ExitLoser,
+            running: false,
+            peers: BTreeMap::new(),
+            owned: BTreeMap::new(),
+            locks: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            next_lock: 0,
+        }
+    }
+
+    /// Sets how a name registered on both sides of a healed netsplit is resolved. (Default exit loser)
+    ///
+    /// Every node should use the same conflict resolution.
+    pub fn conflict(mut self, conflict: GlobalConflict) -> Self {
+        self.conflict = conflict;
+        self
+    }
+
+    /// Registers the local process `pid` under the global `name`.
+    ///
+    /// Fails if the name is registered anywhere in the cluster, or is still locked by another node after retrying.
+    pub async fn register<N: Into<String>>(name: N, pid: Pid) -> Result<(), GlobalNamesError> {
+        use GlobalNamesMessage::*;
+
+        if pid.is_remote() {
+            return Err(GlobalNamesError::NotLocal);
+        }
+
+        let name = name.into();
+
+        for attempt in 1..=GLOBAL_NAMES_RETRIES {
+            match GlobalNames::call(GLOBAL_NAMES_SERVER, Register(name.clone(), pid), None).await? {
+                RegisterSuccess => return Ok(()),
+                RegisterError(GlobalNamesError::Locked) => {
+                    // Jitter the backoff so that nodes racing for the same name don't retry in lockstep.
+                    let jitter = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|now| now.subsec_nanos() as u64 % 10)
+                        .unwrap_or_default();
+
+                    Process::sleep(Duration::from_millis(attempt * 10 + jitter)).await;
+                }
+                RegisterError(error) => return Err(error),
+                _ => unreachable!(),
+            }
+        }
+
+        Err(GlobalNamesError::Locked)
+    }
+
+    /// Removes the global `name`, returning the process it was registered to.
+    pub async fn unregister<N: Into<String>>(name: N) -> Result<Option<Pid>, GlobalNamesError> {
+        use GlobalNamesMessage::*;
+
+        match GlobalNames::call(GLOBAL_NAMES_SERVER, Unregister(name.into()), None).await? {
+            UnregisterSuccess(pid) => Ok(pid),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the process registered under the global `name`, wherever it lives.
+    ///
+    /// This queries the local view of the global names without hitting the server process.
+    pub fn whereis<N: AsRef<str>>(name: N) -> Option<Pid> {
+        global_name_lookup(name.as_ref())
+    }
+
+    /// Returns every globally registered name and it's process.
+    pub fn registered() -> Vec<(String, Pid)> {
+        GLOBAL_NAMES
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect()
+    }
+
+    /// Create a global name server not linked to a supervision tree.
+    pub async fn start(self) -> Result<Pid, ExitReason> {
+        GenServer::start(self, GenServerOptions::new().name(GLOBAL_NAMES_SERVER)).await
+    }
+
+    /// Creates a global name server as part of a supervision tree.
+    pub async fn start_link(self) -> Result<Pid, ExitReason> {
+        GenServer::start_link(self, GenServerOptions::new().name(GLOBAL_NAMES_SERVER)).await
+    }
+
+    /// Builds a child specification for this [GlobalNames] server.
+    pub fn child_spec(self) -> ChildSpec {
+        let conflict = self.conflict;
+
+        ChildSpec::new("GlobalNames")
+            .start(move || GlobalNames::new().conflict(conflict).start_link())
+            .child_type(ChildType::Worker)
+    }
+
+    /// Locks the name on every peer before registering it.
+    fn register_name(
+        &mut self,
+        name: String,
+        pid: Pid,
+        from: From,
+    ) -> Result<(), GlobalNamesError> {
+        if let Some(registered) = global_name_lookup(&name) {
+            return Err(GlobalNamesError::AlreadyRegistered(registered));
+        }
+
+        if self.locks.contains_key(&name) {
+            return Err(GlobalNamesError::Locked);
+        }
+
+        let current = Process::current();
+        let id = self.next_lock;
+
+        self.next_lock += 1;
+
+        self.locks.insert(name.clone(), (current, id));
+
+        for peer in self.peers.keys() {
+            GlobalNames::cast(*peer, GlobalNamesMessage::Lock(current, id, name.clone()));
+        }
+
+        self.pending.insert(
+            id,
+            GlobalNamesPending {
+                from,
+                name,
+                pid,
+                waiting: self.peers.keys().copied().collect(),
+                granted: Vec::new(),
+                denied: false,
+            },
+        );
+
+        self.register_complete(id);
+
+        Ok(())
+    }
+
+    /// Completes a registration once every peer replied.
+    fn register_complete(&mut self, id: u64) {
+        if self
+            .pending
+            .get(&id)
+            .is_some_and(|pending| !pending.waiting.is_empty())
+        {
+            return;
+        }
+
+        let Some(pending) = self.pending.remove(&id) else {
+            return;
+        };
+
+        let current = Process::current();
+
+        // The lock may have been given to a lower ranked server that is racing for the same name.
+        self.unlock(&pending.name, (current, id));
+
+        if pending.denied {
+            for peer in pending.granted {
+                GlobalNames::cast(
+                    peer,
+                    GlobalNamesMessage::Unlock(current, id, pending.name.clone()),
+                );
+            }
+
+            GlobalNames::reply(
+                pending.from,
+                GlobalNamesMessage::RegisterError(GlobalNamesError::Locked),
+            );
+
+            return;
+        }
+
+        let monitor = Process::monitor(pending.pid);
+
+        self.owned
+            .insert(pending.name.clone(), (pending.pid, monitor));
+
+        GLOBAL_NAMES.insert(pending.name.clone(), pending.pid);
+
+        for peer in self.peers.keys() {
+            GlobalNames::cast(
+                *peer,
+                GlobalNamesMessage::PeerRegister(current, id, pending.name.clone(), pending.pid),
+            );
+        }
+
+        GlobalNames::reply(pending.from, GlobalNamesMessage::RegisterSuccess);
+    }
+
+    /// Records a peer's reply to a lock request.
+    fn lock_reply(&mut self, peer: Pid, id: u64, granted: bool) {
+        let Some(pending) = self.pending.get_mut(&id) else {
+            return;
+        };
+
+        if !pending.waiting.remove(&peer) {
+            return;
+        }
+
+        if granted {
+            pending.granted.push(peer);
+        } else {
+            pending.denied = true;
+        }
+
+        self.register_complete(id);
+    }
+
+    /// Removes a global name, and notifies every peer.
+    fn unregister_name(&mut self, name: String) -> Option<Pid> {
+        let (_, pid) = GLOBAL_NAMES.remove(&name)?;
+
+        if let Some((_, monitor)) = self.owned.remove(&name) {
+            Process::demonitor(monitor);
+        }
+
+        for peer in self.peers.keys() {
+            GlobalNames::cast(*peer, GlobalNamesMessage::PeerUnregister(name.clone(), pid));
+        }
+
+        Some(pid)
+    }
+
+    /// Removes every name of a local process that exited, and notifies every peer.
+    fn local_down(&mut self, pid: Pid) {
+        let names: Vec<String> = self
+            .owned
+            .iter()
+            .filter(|(_, (owner, _))| *owner == pid)
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for name in names {
+            self.owned.remove(&name);
+
+            GLOBAL_NAMES.remove_if(&name, |_, registered| *registered == pid);
+
+            for peer in self.peers.keys() {
+                GlobalNames::cast(*peer, GlobalNamesMessage::PeerUnregister(name.clone(), pid));
+            }
+        }
+    }
+
+    /// Sends our server process to the server on the given node.
+    fn discover(&self, node: Node) {
+        GlobalNames::cast(
+            (GLOBAL_NAMES_SERVER, node),
+            GlobalNamesMessage::Discover(Process::current()),
+        );
+    }
+
+    /// Starts tracking a peer server process if it's not known yet, returns `true` if it's new.
+    fn peer_insert(&mut self, peer: Pid) -> bool {
+        if self.peers.contains_key(&peer) {
+            return false;
+        }
+
+        self.peers.insert(peer, Process::monitor(peer));
+
+        true
+    }
+
+    /// Merges the names owned by a peer into our view, resolving any conflicts.
+    fn peer_sync(&mut self, peer: Pid, names: Vec<(String, Pid)>) {
+        self.peer_insert(peer);
+
+        for (name, pid) in names {
+            match global_name_lookup(&name) {
+                Some(registered) if registered == pid => {}
+                Some(registered) => self.resolve_conflict(name, registered, pid),
+                None => {
+                    GLOBAL_NAMES.insert(name, pid);
+                }
+            }
+        }
+    }
+
+    /// Resolves a name registered to two processes, the same way on every node.
+    fn resolve_conflict(&mut self, name: String, registered: Pid, incoming: Pid) {
+        let (winner, loser) = if global_rank(registered) <= global_rank(incoming) {
+            (registered, incoming)
+        } else {
+            (incoming, registered)
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(name = ?name, winner = ?winner, loser = ?loser, conflict = ?self.conflict, "Resolving global name conflict");
+
+        let mut release = |pid: Pid| {
+            if self
+                .owned
+                .get(&name)
+                .is_some_and(|(owner, _)| *owner == pid)
+            {
+                if let Some((_, monitor)) = self.owned.remove(&name) {
+                    Process::demonitor(monitor);
+                }
+
+                return true;
+            }
+
+            false
+        };
+
+        match self.conflict {
+            GlobalConflict::ExitLoser => {
+                if release(loser) {
+                    Process::exit(loser, ExitReason::from("name_conflict"));
+                }
+
+                GLOBAL_NAMES.insert(name, winner);
+            }
+            GlobalConflict::UnregisterLoser => {
+                release(loser);
+
+                GLOBAL_NAMES.insert(name, winner);
+            }
+            GlobalConflict::UnregisterBoth => {
+                release(winner);
+                release(loser);
+
+                GLOBAL_NAMES.remove(&name);
+            }
+        }
+    }
+
+    /// Removes the lock on the given name if it's held by the given owner.
+    fn unlock(&mut self, name: &str, owner: (Pid, u64)) {
+        if self.locks.get(name).is_some_and(|lock| *lock == owner) {
+            self.locks.remove(name);
+        }
+    }
+
+    /// Removes every name, lock, and pending lock of a peer server that went down.
+    fn peer_down(&mut self, peer: Pid) {
+        if self.peers.remove(&peer).is_none() {
+            return;
+        }
+
+        GLOBAL_NAMES.retain(|_, pid| pid.is_local() || pid.node() != peer.node());
+
+        self.locks.retain(|_, (owner, _)| *owner != peer);
+
+        let waiting: Vec<u64> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.waiting.contains(&peer))
+            .map(|(id, _)| *id)
+            .collect();
+
+        // A peer that went down can't hold the name, so it's treated as granted.
+        for id in waiting {
+            if let Some(pending) = self.pending.get_mut(&id) {
+                pending.waiting.remove(&peer);
+            }
+
+            self.register_complete(id);
+        }
+    }
+}
+
+impl Default for GlobalNames {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for GlobalNames {
+    fn drop(&mut self) {
+        // A server that failed to start must not clear the view of the running server.
+        if self.running {
+            GLOBAL_NAMES.clear();
+        }
+    }
+}
+
+impl GenServer for GlobalNames {
+    type Message = GlobalNamesMessage;
+
+    async fn init(&mut self) -> Result<(), ExitReason> {
+        self.running = true;
+
+        Node::monitor_all();
+
+        for node in Node::list() {
+            self.discover(node);
+        }
+
+        Ok(())
+    }
+
+    async fn handle_cast(&mut self, message: Self::Message) -> Result<(), ExitReason> {
+        use GlobalNamesMessage::*;
+
+        let current = Process::current();
+
+        match message {
+            Discover(peer) => {
+                if self.peer_insert(peer) {
+                    GlobalNames::cast(peer, Discover(current));
+                }
+
+                let owned = self
+                    .owned
+                    .iter()
+                    .map(|(name, (pid, _))| (name.clone(), *pid))
+                    .collect();
+
+                GlobalNames::cast(peer, Sync(current, owned));
+            }
+            Sync(peer, names) => {
+                self.peer_sync(peer, names);
+            }
+            Lock(peer, id, name) => {
+                let granted = !GLOBAL_NAMES.contains_key(&name)
+                    && self
+                        .locks
+                        .get(&name)
+                        .is_none_or(|lock| *lock == (peer, id) || lock_wins(peer, lock.0));
+
+                if granted {
+                    if let Some((owner, owner_id)) = self.locks.insert(name, (peer, id)) {
+                        // We lost the race for the name, so our own registration must fail.
+                        if owner == current {
+                            if let Some(pending) = self.pending.get_mut(&owner_id) {
+                                pending.denied = true;
+                            }
+                        }
+                    }
+                }
+
+                GlobalNames::cast(peer, LockReply(current, id, granted));
+            }
+            LockReply(peer, id, granted) => {
+                self.lock_reply(peer, id, granted);
+            }
+            Unlock(peer, id, name) => {
+                self.unlock(&name, (peer, id));
+            }
+            PeerRegister(peer, id, name, pid) => {
+                self.unlock(&name, (peer, id));
+
+                GLOBAL_NAMES.insert(name, pid);
+            }
+            PeerUnregister(name, pid) => {
+                if self
+                    .owned
+                    .get(&name)
+                    .is_some_and(|(owner, _)| *owner == pid)
+                {
+                    if let Some((_, monitor)) = self.owned.remove(&name) {
+                        Process::demonitor(monitor);
+                    }
+                }
+
+                GLOBAL_NAMES.remove_if(&name, |_, registered| *registered == pid);
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    async fn handle_call(
+        &mut self,
+        message: Self::Message,
+        from: From,
+    ) -> Result<Option<Self::Message>, ExitReason> {
+        use GlobalNamesMessage::*;
+
+        match message {
+            Register(name, pid) => match self.register_name(name, pid, from) {
+                // The reply is sent once every peer has locked the name.
+                Ok(()) => Ok(None),
+                Err(error) => Ok(Some(RegisterError(error))),
+            },
+            Unregister(name) => {
+                let pid = self.unregister_name(name);
+
+                Ok(Some(UnregisterSuccess(pid)))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn handle_info(&mut self, info: Message<Self::Message>) -> Result<(), ExitReason> {
+        match info {
+            Message::System(SystemMessage::ProcessDown(Dest::Pid(pid), _, _)) => {
+                if pid.is_local() {
+                    self.local_down(pid);
+                } else {
+                    self.peer_down(pid);
+                }
+
+                Ok(())
+            }
+            Message::System(SystemMessage::NodeUp(node, _)) => {
+                self.discover(node);
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::convert::From<CallError> for GlobalNamesError {
+    fn from(value: CallError) -> Self {
+        Self::CallError(value)
+    }
+}
+
+/// Looks up the process registered under the given global name.
+pub(crate) fn global_name_lookup(name: &str) -> Option<Pid> {
+    GLOBAL_NAMES.get(name).map(|pid| *pid)
+}
+
+/// Returns `true` if a lock requested by the given server wins over a lock held by another server.
+///
+/// Servers racing for the same name always give the lock to the lowest ranked server, so that one of them gets the name.
+fn lock_wins(requested: Pid, held: Pid) -> bool {
+    match (global_rank(requested), global_rank(held)) {
+        (Some(requested), Some(held)) => requested < held,
+        _ => false,
+    }
+}
+
+/// Returns a rank for the given process that is the same on every node.
+fn global_rank(pid: Pid) -> Option<(String, SocketAddr, u64)> {
+    let (name, address) = if pid.is_local() {
+        node_lookup_local()?
+    } else {
+        node_lookup_remote(pid.node())?
+    };
+
+    Some((name, address, pid.id()))
+}
@@ -43,8 +43,21 @@ where
 {
     /// Constructs a new instance of [HashRing].
     pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<T, S> HashRing<T, S>
+where
+    T: Clone,
+    S: BuildHasher,
+{
+    /// Constructs a new instance of [HashRing] which hashes keys with the given hasher.
+    ///
+    /// Rings on different nodes only agree on the node for a key when they use a deterministic hasher.
+    pub fn with_hasher(hash_builder: S) -> Self {
         Self {
-            hash_builder: RandomState::new(),
+            hash_builder,
             ring: ArcSwap::new(Arc::new(Vec::new())),
             ring_lock: Mutex::new(()),
             overrides: DashMap::new(),
@@ -1,32 +1,49 @@
+mod agent;
 mod alias;
 mod application;
 mod application_config;
 mod argument_error;
+mod async_stream_options;
 mod call_error;
 mod catch_unwind;
 mod child_spec;
+mod compression;
 mod dest;
+mod exit_panic;
 mod exit_reason;
-mod frame;
+#[doc(hidden)]
+pub mod frame;
 mod from;
 mod gen_server;
 mod gen_server_options;
+mod global_conflict;
+mod global_names;
 mod hash_ring;
 mod link;
 mod local;
+mod memory_transport;
 mod message;
+mod message_format;
 mod monitor;
 mod node;
+mod node_fragments;
+mod node_info;
 mod node_kernel;
+mod node_lanes;
 mod node_local;
 mod node_options;
 mod node_registration;
 mod node_registry;
 mod node_remote;
 mod node_state;
+mod node_stats;
+mod node_transport;
+mod on_timeout;
 mod pid;
 mod process;
+mod process_dictionary;
 mod process_flags;
+mod process_groups;
 mod process_info;
 mod process_item;
 mod process_kernel;
@@ -34,59 +51,98 @@ mod process_monitor;
 mod process_receiver;
 mod process_registration;
 mod process_registry;
+mod process_stats;
 mod receivable;
+mod reconnect_policy;
 mod reference;
 mod registry;
+mod registry_event;
 mod registry_options;
+mod registry_ring;
 mod restart;
 mod semaphore;
 mod serialize;
 mod shutdown;
+mod spawn_options;
 mod supervisor;
 mod supervisor_options;
 mod system_message;
 mod task;
+mod task_stream;
+mod task_supervisor;
+mod tcp_transport;
 mod timeout;
 
+#[cfg(unix)]
+mod unix_transport;
+
 #[cfg(feature = "console")]
 mod console;
 #[cfg(feature = "console")]
 mod runtime_info;
 
+pub use agent::*;
 pub use application::*;
 pub use application_config::*;
 pub use argument_error::*;
+pub use async_stream_options::*;
 pub use call_error::*;
 pub use child_spec::*;
+pub use compression::*;
 pub use dest::*;
+pub use exit_panic::*;
 pub use exit_reason::*;
 pub use from::*;
 pub use gen_server::*;
 pub use gen_server_options::*;
+pub use global_conflict::*;
+pub use global_names::*;
 pub use hash_ring::*;
 pub use local::*;
+pub use memory_transport::*;
 pub use message::*;
+pub use message_format::*;
 pub use node::*;
+#[doc(hidden)]
+pub use node_fragments::*;
+pub use node_info::*;
+#[doc(hidden)]
+pub use node_lanes::*;
 pub use node_options::*;
 pub use node_state::*;
+#[doc(hidden)]
+pub use node_stats::*;
+pub use node_transport::*;
+pub use on_timeout::*;
 pub use pid::*;
 pub use process::*;
 pub use process_flags::*;
+pub use process_groups::*;
 pub use process_info::*;
 pub use process_receiver::*;
 pub use receivable::*;
+pub use reconnect_policy::*;
 pub use reference::*;
 pub use registry::*;
+pub use registry_event::*;
 pub use registry_options::*;
+#[doc(hidden)]
+pub use registry_ring::*;
 pub use restart::*;
 pub use semaphore::*;
 pub use shutdown::*;
+pub use spawn_options::*;
 pub use supervisor::*;
 pub use supervisor_options::*;
 pub use system_message::*;
 pub use task::*;
+pub use task_supervisor::*;
+pub use tcp_transport::*;
 pub use timeout::*;
 
+#[cfg(unix)]
+pub use unix_transport::*;
+
 #[cfg(feature = "macros")]
 pub use hydra_macros::main;
 #[cfg(feature = "macros")]
@@ -106,9 +162,12 @@ pub(crate) use node_local::*;
 pub(crate) use node_registration::*;
 pub(crate) use node_registry::*;
 pub(crate) use node_remote::*;
+pub(crate) use process_dictionary::*;
 pub(crate) use process_item::*;
 pub(crate) use process_kernel::*;
 pub(crate) use process_monitor::*;
 pub(crate) use process_registration::*;
 pub(crate) use process_registry::*;
+pub(crate) use process_stats::*;
 pub(crate) use serialize::*;
+pub(crate) use task_stream::*;
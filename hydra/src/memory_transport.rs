@@ -0,0 +1,108 @@
+use std::io;
+use std::net::SocketAddr;
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use once_cell::sync::Lazy;
+
+use tokio::io::duplex;
+use tokio::io::DuplexStream;
+
+use crate::NodeListener;
+use crate::NodeStream;
+use crate::NodeTransport;
+
+/// A collection of in-memory listeners by address.
+static MEMORY_LISTENERS: Lazy<DashMap<SocketAddr, flume::Sender<DuplexStream>>> =
+    Lazy::new(DashMap::new);
+
+/// A transport that connects nodes in the same process over in-memory streams, useful for tests.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryTransport {
+    buffer_size: usize,
+}
+
+impl MemoryTransport {
+    /// Constructs a new instance of [MemoryTransport].
+    pub const fn new() -> Self {
+        Self {
+            buffer_size: 64 * 1024,
+        }
+    }
+
+    /// Sets the number of bytes buffered in each direction of a connection. (Default 64kb)
+    pub const fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+}
+
+impl Default for MemoryTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Listens for in-memory connections on a given address.
+struct MemoryListener {
+    receiver: flume::Receiver<DuplexStream>,
+}
+
+impl NodeTransport for MemoryTransport {
+    fn listen(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeListener>>> {
+        let result = match MEMORY_LISTENERS.entry(address) {
+            Entry::Occupied(mut entry) => {
+                // The previous listener on this address was dropped, so it can be replaced.
+                if entry.get().is_disconnected() {
+                    let (sender, receiver) = flume::unbounded();
+
+                    entry.insert(sender);
+
+                    Ok(MemoryListener { receiver })
+                } else {
+                    Err(io::Error::from(io::ErrorKind::AddrInUse))
+                }
+            }
+            Entry::Vacant(entry) => {
+                let (sender, receiver) = flume::unbounded();
+
+                entry.insert(sender);
+
+                Ok(MemoryListener { receiver })
+            }
+        };
+
+        async move { result.map(|listener| Box::new(listener) as Box<dyn NodeListener>) }.boxed()
+    }
+
+    fn connect(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeStream>>> {
+        let (local, remote) = duplex(self.buffer_size);
+
+        let result = match MEMORY_LISTENERS.get(&address) {
+            Some(sender) => sender
+                .send(remote)
+                .map(|_| Box::new(local) as Box<dyn NodeStream>)
+                .map_err(|_| io::Error::from(io::ErrorKind::ConnectionRefused)),
+            None => Err(io::Error::from(io::ErrorKind::ConnectionRefused)),
+        };
+
+        async move { result }.boxed()
+    }
+}
+
+impl NodeListener for MemoryListener {
+    fn accept(&mut self) -> BoxFuture<'_, io::Result<Box<dyn NodeStream>>> {
+        async move {
+            self.receiver
+                .recv_async()
+                .await
+                .map(|socket| Box::new(socket) as Box<dyn NodeStream>)
+                .map_err(|_| io::Error::from(io::ErrorKind::NotConnected))
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,87 @@
+use std::any::TypeId;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+
+use dashmap::DashMap;
+
+use once_cell::sync::Lazy;
+
+use crate::Receivable;
+
+/// The wire id of the format used for message types without a registered format.
+static MESSAGE_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+/// The formats registered for specific message types.
+static MESSAGE_FORMATS: Lazy<DashMap<TypeId, MessageFormat>> = Lazy::new(DashMap::new);
+
+/// The format used to serialize messages sent to remote processes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// MessagePack with field names, which tolerates added, removed, and reordered fields.
+    #[default]
+    MessagePack,
+    /// MessagePack without field names, which is smaller but requires both nodes to agree on the field order.
+    MessagePackCompact,
+    /// Bincode, which is the smallest and fastest, but doesn't support types that need a self describing format.
+    Bincode,
+    /// CBOR, which is self describing like MessagePack with field names.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl MessageFormat {
+    /// Registers the format used to serialize messages of the given type, instead of the node message format.
+    ///
+    /// Every node receiving messages of this type must support the format.
+    pub fn register<T: Receivable>(format: MessageFormat) {
+        MESSAGE_FORMATS.insert(TypeId::of::<T>(), format);
+    }
+
+    /// Returns the wire id of this format.
+    pub(crate) const fn id(&self) -> u8 {
+        match self {
+            Self::MessagePack => 0,
+            Self::MessagePackCompact => 1,
+            Self::Bincode => 2,
+            #[cfg(feature = "cbor")]
+            Self::Cbor => 3,
+        }
+    }
+
+    /// Returns the format with the given wire id, if this node supports it.
+    pub(crate) const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::MessagePack),
+            1 => Some(Self::MessagePackCompact),
+            2 => Some(Self::Bincode),
+            #[cfg(feature = "cbor")]
+            3 => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Returns the wire ids of every format this node can deserialize.
+    pub(crate) fn supported() -> Vec<u8> {
+        vec![
+            Self::MessagePack.id(),
+            Self::MessagePackCompact.id(),
+            Self::Bincode.id(),
+            #[cfg(feature = "cbor")]
+            Self::Cbor.id(),
+        ]
+    }
+
+    /// Sets the format used for message types without a registered format.
+    pub(crate) fn set_default(format: MessageFormat) {
+        MESSAGE_FORMAT.store(format.id(), Ordering::Relaxed);
+    }
+
+    /// Returns the format used to serialize messages of the given type.
+    pub(crate) fn for_type<T: Receivable>() -> Self {
+        if let Some(format) = MESSAGE_FORMATS.get(&TypeId::of::<T>()) {
+            return *format;
+        }
+
+        Self::from_id(MESSAGE_FORMAT.load(Ordering::Relaxed)).unwrap_or_default()
+    }
+}
@@ -9,6 +9,7 @@ use crate::frame::MonitorDown;
 
 use crate::alias_destroy;
 use crate::node_lookup_remote;
+use crate::node_monitor_all_destroy;
 use crate::node_monitor_destroy;
 use crate::node_process_monitor_create;
 use crate::node_process_monitor_destroy;
@@ -84,6 +85,9 @@ pub fn monitor_destroy_all<'a, M: IntoIterator<Item = (&'a Reference, &'a Proces
             ProcessMonitor::ForNode(node) => {
                 node_monitor_destroy(node.clone(), *reference);
             }
+            ProcessMonitor::ForAllNodes => {
+                node_monitor_all_destroy(*reference);
+            }
         }
 
         if reference.is_local() {
@@ -111,6 +115,18 @@ pub fn monitor_install(process: Dest, reference: Reference, from: Pid) {
         alias_destroy(reference);
     };
 
+    // Global names are monitored through the process they're registered to.
+    let Some(process) = process.resolve() else {
+        PROCESS.with(|process| {
+            process
+                .monitors
+                .borrow_mut()
+                .insert(reference, ProcessMonitor::ForProcess(None))
+        });
+
+        return send_process_down(dest, ExitReason::from("noproc"));
+    };
+
     match process {
         Dest::Pid(pid) => {
             if pid == from {
@@ -127,7 +143,7 @@ pub fn monitor_install(process: Dest, reference: Reference, from: Pid) {
             if pid.is_local() {
                 process_exists_lock(pid, |exists| {
                     if exists {
-                        monitor_create(pid, reference, from, Some(process));
+                        monitor_create(pid, reference, from, Some(dest.clone()));
                     } else {
                         send_process_down(dest, ExitReason::from("noproc"));
                     }
@@ -205,6 +221,7 @@ pub fn monitor_install(process: Dest, reference: Reference, from: Pid) {
             }
         }
         Dest::Alias(_) => panic!("Can not monitor an alias!"),
+        Dest::Global(_) => unreachable!(),
     }
 }
 
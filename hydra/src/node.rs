@@ -7,20 +7,26 @@ use serde::Serialize;
 use crate::node_alive;
 use crate::node_disconnect;
 use crate::node_forget;
+use crate::node_info;
 use crate::node_list;
 use crate::node_list_filtered;
 use crate::node_local_start;
 use crate::node_local_stop;
 use crate::node_lookup_local;
+use crate::node_monitor_all_create;
+use crate::node_monitor_all_destroy;
 use crate::node_monitor_create;
 use crate::node_monitor_destroy;
 use crate::node_register;
 use crate::node_set_cookie;
+use crate::node_set_reconnect_policy;
+use crate::NodeInfo;
 use crate::NodeOptions;
 use crate::NodeState;
 use crate::Pid;
 use crate::Process;
 use crate::ProcessMonitor;
+use crate::ReconnectPolicy;
 use crate::Reference;
 use crate::PROCESS;
 
@@ -86,6 +92,21 @@ impl Node {
         node_register(Node::from((name, address)), true);
     }
 
+    /// Connects to the given node if we're not already connected, reconnecting with the given policy instead of the one the local node was started with.
+    pub fn connect_with_policy<T: Into<Node>>(node: T, policy: ReconnectPolicy) {
+        let node = node.into();
+
+        let Node::Remote(name, address) = node else {
+            panic!("Can't connect to self!");
+        };
+
+        let node = Node::from((name, address));
+
+        node_register(node.clone(), false);
+        node_set_reconnect_policy(node.clone(), policy);
+        node_register(node, true);
+    }
+
     /// Forcefully disconnects from the given node.
     pub fn disconnect<T: Into<Node>>(node: T) {
         let node = node.into();
@@ -142,6 +163,11 @@ impl Node {
         node_list_filtered(state)
     }
 
+    /// Returns connection information for the given remote node, or [None] if the node isn't known.
+    pub fn info<T: Into<Node>>(node: T) -> Option<NodeInfo> {
+        node_info(node.into())
+    }
+
     /// Monitors the given node. If we're not currently connected, an attempt is made to connect.
     pub fn monitor<T: Into<Node>>(node: T) -> Reference {
         let current = Process::current();
@@ -166,6 +192,25 @@ impl Node {
         reference
     }
 
+    /// Monitors every node, receiving a `NodeUp` or `NodeDown` system message as nodes connect and disconnect.
+    ///
+    /// The monitor stays installed until [Node::demonitor] is called.
+    pub fn monitor_all() -> Reference {
+        let current = Process::current();
+        let reference = Reference::new();
+
+        PROCESS.with(|process| {
+            process
+                .monitors
+                .borrow_mut()
+                .insert(reference, ProcessMonitor::ForAllNodes)
+        });
+
+        node_monitor_all_create(reference, current);
+
+        reference
+    }
+
     /// Demonitors the monitor identified by the given reference.
     pub fn demonitor(monitor: Reference) {
         let Some(process_monitor) =
@@ -174,11 +219,11 @@ impl Node {
             return;
         };
 
-        let ProcessMonitor::ForNode(node) = process_monitor else {
-            panic!("Invalid node monitor reference!");
-        };
-
-        node_monitor_destroy(node, monitor);
+        match process_monitor {
+            ProcessMonitor::ForNode(node) => node_monitor_destroy(node, monitor),
+            ProcessMonitor::ForAllNodes => node_monitor_all_destroy(monitor),
+            _ => panic!("Invalid node monitor reference!"),
+        }
     }
 }
 
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::frame::Fragment;
+use crate::frame::Send;
+use crate::frame::MAX_FRAGMENTED_MESSAGES;
+
+use crate::ExitReason;
+
+/// Inbound fragments of large messages from a remote node, which are reassembled into the original messages.
+///
+/// The number of messages being reassembled at once, and the size of each message, are bounded,
+/// so that a remote node can't exhaust our memory with partial messages.
+pub struct NodeFragments {
+    /// The maximum size of a reassembled message.
+    max_message_size: usize,
+    /// The data received so far for each message being reassembled.
+    messages: HashMap<u64, Vec<u8>>,
+}
+
+impl NodeFragments {
+    /// Constructs a new instance of [NodeFragments] which rejects messages larger than `max_message_size`.
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            max_message_size,
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Adds a fragment, returning the reassembled [Send] frame once the last fragment of a message is added.
+    ///
+    /// Returns the reason to disconnect the remote node if the limits are exceeded, or the message can't be decoded.
+    pub fn push(&mut self, fragment: Fragment) -> Result<Option<Send>, ExitReason> {
+        if !self.messages.contains_key(&fragment.id)
+            && self.messages.len() >= MAX_FRAGMENTED_MESSAGES
+        {
+            return Err(ExitReason::from("too_many_fragments"));
+        }
+
+        let data = self.messages.entry(fragment.id).or_default();
+
+        data.extend_from_slice(&fragment.data);
+
+        if data.len() > self.max_message_size {
+            return Err(ExitReason::from("message_too_large"));
+        }
+
+        if !fragment.last {
+            return Ok(None);
+        }
+
+        let data = self.messages.remove(&fragment.id).unwrap_or_default();
+
+        Fragment::join(&data)
+            .map(Some)
+            .map_err(|_| ExitReason::from("decode_error"))
+    }
+
+    /// Returns the number of messages being reassembled.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether or not there are no messages being reassembled.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::NodeState;
+
+/// Debug information for a specific node.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NodeInfo {
+    /// The state of the node.
+    pub state: NodeState,
+    /// Whether or not the node is hidden.
+    pub hidden: bool,
+    /// The last measured heartbeat round trip time, if any.
+    pub round_trip_time: Option<Duration>,
+    /// The last time anything was received from the node, if ever.
+    pub last_seen: Option<SystemTime>,
+    /// The number of bytes received from the node on the most recent connection.
+    pub bytes_in: u64,
+    /// The number of bytes sent to the node on the most recent connection.
+    pub bytes_out: u64,
+    /// The number of frames received from the node on the most recent connection.
+    pub frames_in: u64,
+    /// The number of frames sent to the node on the most recent connection.
+    pub frames_out: u64,
+    /// The number of messages dropped because the pending message queue for the node was full.
+    pub dropped_messages: u64,
+}
+
+impl NodeInfo {
+    /// Construct a new empty [NodeInfo] for a node in the given state.
+    pub const fn new(state: NodeState) -> Self {
+        Self {
+            state,
+            hidden: false,
+            round_trip_time: None,
+            last_seen: None,
+            bytes_in: 0,
+            bytes_out: 0,
+            frames_in: 0,
+            frames_out: 0,
+            dropped_messages: 0,
+        }
+    }
+}
@@ -10,6 +10,7 @@ use crate::process_name_lookup;
 use crate::process_sender;
 use crate::serialize_value;
 use crate::ExitReason;
+use crate::MessageFormat;
 use crate::Node;
 use crate::Pid;
 use crate::ProcessItem;
@@ -17,29 +18,33 @@ use crate::Receivable;
 use crate::Reference;
 
 /// Forwards an incoming nodes send frame message to the target if it exists.
-pub fn node_forward_send(mut send: Send) {
-    let is_single_target = send.targets.len() == 1;
+pub fn node_forward_send(send: Send) {
+    let Some(format) = MessageFormat::from_id(send.format) else {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(format = ?send.format, "Dropped message, the message format isn't supported");
+
+        return;
+    };
 
     for target in send.targets {
-        let message = if is_single_target {
-            std::mem::take(&mut send.message)
-        } else {
-            send.message.clone()
-        };
+        let message = send.message.clone();
 
         match target {
             SendTarget::Pid(id) => {
                 process_sender(Pid::local(id.get()))
-                    .map(|sender| sender.send(ProcessItem::UserRemoteMessage(message)));
+                    .map(|sender| sender.send(ProcessItem::UserRemoteMessage(format, message)));
             }
             SendTarget::Named(name) => {
                 process_name_lookup(&name)
                     .and_then(process_sender)
-                    .map(|sender| sender.send(ProcessItem::UserRemoteMessage(message)));
+                    .map(|sender| sender.send(ProcessItem::UserRemoteMessage(format, message)));
             }
             SendTarget::Alias(alias) => {
-                alias_retrieve(Reference::Local(alias))
-                    .map(|alias| alias.sender.send(ProcessItem::UserRemoteMessage(message)));
+                alias_retrieve(Reference::Local(alias)).map(|alias| {
+                    alias
+                        .sender
+                        .send(ProcessItem::UserRemoteMessage(format, message))
+                });
             }
         }
     }
@@ -58,9 +63,9 @@ pub fn node_process_send_with_pid<M: Receivable>(pid: Pid, message: M) {
         panic!("Can't send to a local process!");
     };
 
-    let message = serialize_value(&message);
+    let (format, message) = serialize_value(&message);
 
-    node_send_frame(Frame::from(Send::with_pid(id, message)), node);
+    node_send_frame(Frame::from(Send::with_pid(id, format, message)), node);
 }
 
 /// Sends the given message to the remote node with the given alias.
@@ -69,16 +74,16 @@ pub fn node_process_send_with_alias<M: Receivable>(reference: Reference, message
         panic!("Can't send to a local alias!");
     };
 
-    let message = serialize_value(&message);
+    let (format, message) = serialize_value(&message);
 
-    node_send_frame(Frame::from(Send::with_alias(id, message)), node);
+    node_send_frame(Frame::from(Send::with_alias(id, format, message)), node);
 }
 
 /// Sends the given message to the remote node with the given name/node.
 pub fn node_process_send_with_name<M: Receivable>(name: String, node: Node, message: M) {
-    let message = serialize_value(&message);
+    let (format, message) = serialize_value(&message);
 
     let node = node_register(node, false);
 
-    node_send_frame(Frame::from(Send::with_name(name, message)), node);
+    node_send_frame(Frame::from(Send::with_name(name, format, message)), node);
 }
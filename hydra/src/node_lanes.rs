@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::frame::Fragment;
+use crate::frame::Frame;
+use crate::frame::MAX_FRAGMENTED_MESSAGES;
+
+use crate::NodeOptions;
+
+/// An outbound frame, along with the id of the local process it originates from.
+pub type NodeFrame = (Option<u64>, Frame);
+
+/// Outbound frames waiting to be written to a remote node, split into a priority and a bulk lane.
+///
+/// Control frames skip ahead of user messages, unless the process they originate from still has frames in the bulk lane,
+/// which keeps the frames of every process in the order they were sent. Large messages are split into fragments,
+/// which are interleaved with the rest of the bulk lane, up to [MAX_FRAGMENTED_MESSAGES] at once.
+pub struct NodeLanes {
+    /// Whether or not control frames are prioritized.
+    enabled: bool,
+    /// The size of the fragments a large message is split into.
+    fragment_size: usize,
+    /// The maximum size of an outbound message.
+    max_message_size: usize,
+    /// Frames that are written before anything else.
+    priority: VecDeque<Frame>,
+    /// Frames that are written in the order they were queued.
+    bulk: VecDeque<NodeFrame>,
+    /// Large messages being written one fragment at a time, along with their origin process.
+    fragmented: VecDeque<(Option<u64>, VecDeque<Fragment>)>,
+    /// The number of frames in the bulk lane, or being fragmented, for each origin process.
+    origins: HashMap<u64, usize>,
+    /// The id of the next fragmented message.
+    next_fragment_id: u64,
+    /// Whether or not the next bulk write is a fragment.
+    fragment_turn: bool,
+}
+
+impl NodeLanes {
+    /// Constructs a new instance of [NodeLanes] for the given options.
+    pub fn new(options: &NodeOptions) -> Self {
+        Self {
+            enabled: options.priority_lanes,
+            fragment_size: options.fragment_size,
+            max_message_size: options.max_message_size,
+            priority: VecDeque::new(),
+            bulk: VecDeque::new(),
+            fragmented: VecDeque::new(),
+            origins: HashMap::new(),
+            next_fragment_id: 0,
+            fragment_turn: false,
+        }
+    }
+
+    /// Queues a frame from the given origin process.
+    pub fn push(&mut self, origin: Option<u64>, frame: Frame) {
+        let priority = match &frame {
+            Frame::Ping(_) | Frame::Pong(_) => true,
+            Frame::Send(send) => {
+                if send.message.len() > self.max_message_size {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(size = ?send.message.len(), max_message_size = ?self.max_message_size, "Dropped message, the message is larger than the maximum message size");
+
+                    return;
+                }
+
+                false
+            }
+            _ => self.enabled && origin.is_some_and(|origin| !self.origins.contains_key(&origin)),
+        };
+
+        if priority {
+            self.priority.push_back(frame);
+            return;
+        }
+
+        if let Some(origin) = origin {
+            *self.origins.entry(origin).or_default() += 1;
+        }
+
+        self.bulk.push_back((origin, frame));
+    }
+
+    /// Takes the next frame that should be written.
+    pub fn pop(&mut self) -> Option<Frame> {
+        if let Some(frame) = self.priority.pop_front() {
+            return Some(frame);
+        }
+
+        loop {
+            if !self.fragmented.is_empty()
+                && (self.fragment_turn || self.bulk.is_empty() || self.bulk_blocked())
+            {
+                self.fragment_turn = false;
+
+                return self.pop_fragment();
+            }
+
+            let (origin, frame) = self.bulk.pop_front()?;
+
+            self.fragment_turn = true;
+
+            match frame {
+                Frame::Send(send) if send.message.len() > self.fragment_size => {
+                    let id = self.next_fragment_id;
+
+                    self.next_fragment_id += 1;
+
+                    match Fragment::split(id, send, self.fragment_size) {
+                        Ok(fragments) => {
+                            // The origin stays counted until the last fragment is written.
+                            self.fragmented.push_back((origin, fragments));
+                        }
+                        Err(error) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!(error = ?error, "Dropped message, failed to split the message into fragments");
+
+                            #[cfg(not(feature = "tracing"))]
+                            let _ = error;
+
+                            self.release(origin);
+                        }
+                    }
+                }
+                frame => {
+                    self.release(origin);
+
+                    return Some(frame);
+                }
+            }
+        }
+    }
+
+    /// Whether or not there are no frames waiting to be written.
+    pub fn is_empty(&self) -> bool {
+        self.priority.is_empty() && self.bulk.is_empty() && self.fragmented.is_empty()
+    }
+
+    /// Takes the next fragment, rotating between the fragmented messages.
+    fn pop_fragment(&mut self) -> Option<Frame> {
+        let (origin, mut fragments) = self.fragmented.pop_front()?;
+
+        let fragment = fragments.pop_front()?;
+
+        if fragments.is_empty() {
+            self.release(origin);
+        } else {
+            self.fragmented.push_back((origin, fragments));
+        }
+
+        Some(fragment.into())
+    }
+
+    /// Whether or not the next bulk frame must wait for a fragmented message from the same origin,
+    /// or for room to fragment it.
+    fn bulk_blocked(&self) -> bool {
+        self.bulk.front().is_some_and(|(origin, frame)| {
+            let fragmenting =
+                matches!(frame, Frame::Send(send) if send.message.len() > self.fragment_size);
+
+            (fragmenting && self.fragmented.len() >= MAX_FRAGMENTED_MESSAGES)
+                || self
+                    .fragmented
+                    .iter()
+                    .any(|(fragmented, _)| fragmented == origin)
+        })
+    }
+
+    /// Releases a frame from the given origin once it has left the bulk lane.
+    fn release(&mut self, origin: Option<u64>) {
+        let Some(origin) = origin else {
+            return;
+        };
+
+        if let Some(count) = self.origins.get_mut(&origin) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.origins.remove(&origin);
+            }
+        }
+    }
+}
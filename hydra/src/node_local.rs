@@ -3,8 +3,6 @@ use std::sync::Arc;
 use serde::Deserialize;
 use serde::Serialize;
 
-use tokio::net::TcpListener;
-
 use crate::node_local_panic;
 use crate::node_remote_accepter;
 use crate::Local;
@@ -25,23 +23,25 @@ pub enum NodeLocalSupervisorMessage {
 pub struct NodeLocalSupervisor {
     pub name: String,
     pub options: NodeOptions,
-    #[allow(dead_code)]
     pub process: Pid,
 }
 
 impl Drop for NodeLocalSupervisor {
     fn drop(&mut self) {
-        node_local_panic();
+        node_local_panic(self.process);
     }
 }
 
 async fn node_local_listener(supervisor: Arc<NodeLocalSupervisor>) {
-    let listener = TcpListener::bind(supervisor.options.listen_address)
+    let mut listener = supervisor
+        .options
+        .node_transport()
+        .listen(supervisor.options.listen_address)
         .await
         .expect("Failed to bind socket for local node listener!");
 
     loop {
-        let Ok((socket, _)) = listener.accept().await else {
+        let Ok(socket) = listener.accept().await else {
             continue;
         };
 
@@ -3,6 +3,12 @@ use std::net::Ipv4Addr;
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use crate::Compression;
+use crate::MessageFormat;
+use crate::NodeTransport;
+use crate::ReconnectPolicy;
+use crate::TcpTransport;
+
 /// Options used to configure this node as a distributed node.
 #[derive(Clone, Copy)]
 pub struct NodeOptions {
@@ -11,6 +17,17 @@ pub struct NodeOptions {
     pub(crate) handshake_timeout: Duration,
     pub(crate) heartbeat_interval: Duration,
     pub(crate) heartbeat_timeout: Duration,
+    pub(crate) full_mesh: bool,
+    pub(crate) hidden: bool,
+    pub(crate) reconnect_policy: ReconnectPolicy,
+    pub(crate) max_pending_messages: usize,
+    pub(crate) priority_lanes: bool,
+    pub(crate) fragment_size: usize,
+    pub(crate) max_message_size: usize,
+    pub(crate) compression: Compression,
+    pub(crate) compression_threshold: usize,
+    pub(crate) message_format: MessageFormat,
+    pub(crate) transport: Option<&'static dyn NodeTransport>,
 }
 
 impl NodeOptions {
@@ -22,6 +39,17 @@ impl NodeOptions {
             handshake_timeout: Duration::from_secs(5),
             heartbeat_interval: Duration::from_secs(5),
             heartbeat_timeout: Duration::from_secs(45),
+            full_mesh: false,
+            hidden: false,
+            reconnect_policy: ReconnectPolicy::Never,
+            max_pending_messages: 16384,
+            priority_lanes: true,
+            fragment_size: 64 * 1024,
+            max_message_size: 128 * 1024 * 1024,
+            compression: Compression::None,
+            compression_threshold: 1024,
+            message_format: MessageFormat::MessagePack,
+            transport: None,
         }
     }
 
@@ -51,9 +79,98 @@ impl NodeOptions {
 
     /// Sets the time it takes to consider a remote node down when not receiving any data.
     pub fn heartbeat_timeout(mut self, duration: Duration) -> Self {
-        self.handshake_timeout = duration;
+        self.heartbeat_timeout = duration;
+        self
+    }
+
+    /// Sets whether or not this node connects to the peers of every node it connects to. (Default false)
+    ///
+    /// When enabled, a new connection exchanges the list of connected peers, and each side connects to the peers it doesn't know yet.
+    pub fn full_mesh(mut self, full_mesh: bool) -> Self {
+        self.full_mesh = full_mesh;
+        self
+    }
+
+    /// Sets whether or not this node is hidden. (Default false)
+    ///
+    /// A hidden node is never shared with, or connected to by, the peers of the nodes it connects to.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Sets the policy used to reconnect to a remote node that went down unexpectedly. (Default never)
+    ///
+    /// A node connected with [crate::Node::connect_with_policy] uses its own policy instead.
+    pub fn reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    /// Sets the maximum number of messages kept for a remote node while it's connecting. (Default 16384)
+    ///
+    /// Any message sent once the limit is reached is dropped, and counted in [crate::NodeInfo::dropped_messages].
+    pub fn max_pending_messages(mut self, max_pending_messages: usize) -> Self {
+        self.max_pending_messages = max_pending_messages;
+        self
+    }
+
+    /// Sets whether or not control frames are sent ahead of queued user messages. (Default true)
+    ///
+    /// Frames from the same process are always sent in order, only frames from different processes are reordered.
+    /// Heartbeats are always sent ahead of queued user messages, even when this is disabled, so a busy connection isn't timed out.
+    pub fn priority_lanes(mut self, priority_lanes: bool) -> Self {
+        self.priority_lanes = priority_lanes;
+        self
+    }
+
+    /// Sets the size of the fragments a large message is split into, so that it's interleaved with other messages. (Default 64kb)
+    pub fn fragment_size(mut self, fragment_size: usize) -> Self {
+        self.fragment_size = fragment_size;
+        self
+    }
+
+    /// Sets the maximum size of a message sent to, or received from, a remote node. (Default 128mb)
+    ///
+    /// Larger outbound messages are dropped without notifying the sending process, so bound the size of messages before sending them.
+    /// A remote node sending a larger message, or fragmenting too many messages at once, is disconnected.
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Sets the compression used for frames sent to remote nodes that support it. (Default none)
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the size a frame must exceed before it's compressed. (Default 1kb)
+    pub fn compression_threshold(mut self, compression_threshold: usize) -> Self {
+        self.compression_threshold = compression_threshold;
         self
     }
+
+    /// Sets the format used to serialize messages sent to remote processes, the remote node must support it. (Default named msgpack)
+    pub fn message_format(mut self, message_format: MessageFormat) -> Self {
+        self.message_format = message_format;
+        self
+    }
+
+    /// Sets the transport used to listen for, and connect to, remote nodes. (Default tcp)
+    ///
+    /// The transport is kept for the rest of the program, so that the options stay [Copy].
+    pub fn transport<T: NodeTransport>(mut self, transport: T) -> Self {
+        self.transport = Some(Box::leak(Box::new(transport)));
+        self
+    }
+
+    /// Returns the transport used to listen for, and connect to, remote nodes.
+    pub(crate) fn node_transport(&self) -> &'static dyn NodeTransport {
+        static TCP_TRANSPORT: TcpTransport = TcpTransport::new();
+
+        self.transport.unwrap_or(&TCP_TRANSPORT)
+    }
 }
 
 impl Default for NodeOptions {
@@ -1,7 +1,12 @@
 use std::net::SocketAddr;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 
+use crate::NodeFrame;
 use crate::NodeState;
+use crate::NodeStats;
 use crate::Pid;
+use crate::ReconnectPolicy;
 
 /// Node registration information.
 pub struct NodeRegistration {
@@ -11,12 +16,24 @@ pub struct NodeRegistration {
     pub sender: Option<Pid>,
     /// The process responsible for receiving messages for this node.
     pub receiver: Option<Pid>,
+    /// The outbound frame queue of the sender, along with the process each frame originates from.
+    pub frames: Option<flume::Sender<NodeFrame>>,
     /// The state of this node.
     pub state: NodeState,
     /// The name of this node.
     pub name: String,
     /// The broadcast address of this node.
     pub broadcast_address: SocketAddr,
+    /// Whether or not this node is hidden from the full mesh.
+    pub hidden: bool,
+    /// The number of reconnect attempts made since this node went down.
+    pub reconnect_attempts: u32,
+    /// The reconnect policy for this node, overriding the one the local node was started with.
+    pub reconnect_policy: Option<ReconnectPolicy>,
+    /// The number of messages dropped because the pending message queue for this node was full.
+    pub dropped_messages: AtomicU64,
+    /// The statistics of the most recent connection to this node.
+    pub stats: Option<Arc<NodeStats>>,
 }
 
 impl NodeRegistration {
@@ -31,9 +48,15 @@ impl NodeRegistration {
             supervisor,
             sender: None,
             receiver: None,
+            frames: None,
             state,
             name,
             broadcast_address,
+            hidden: false,
+            reconnect_attempts: 0,
+            reconnect_policy: None,
+            dropped_messages: AtomicU64::new(0),
+            stats: None,
         }
     }
 }
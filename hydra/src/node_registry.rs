@@ -3,7 +3,9 @@ use std::collections::BTreeSet;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
@@ -21,16 +23,20 @@ use crate::process_exit_signal_linked;
 use crate::process_sender;
 use crate::Dest;
 use crate::ExitReason;
-use crate::Local;
+use crate::MessageFormat;
 use crate::Node;
+use crate::NodeFrame;
+use crate::NodeInfo;
 use crate::NodeOptions;
 use crate::NodeRegistration;
-use crate::NodeRemoteSenderMessage;
 use crate::NodeState;
+use crate::NodeStats;
 use crate::Pid;
 use crate::Process;
 use crate::ProcessItem;
+use crate::ReconnectPolicy;
 use crate::Reference;
+use crate::PROCESS;
 
 /// Represents the node id always used for the local node.
 pub const LOCAL_NODE_ID: u64 = 0;
@@ -61,15 +67,21 @@ static NODE_MAP: Lazy<DashMap<Node, u64>> = Lazy::new(DashMap::new);
 static NODE_MONITORS: Lazy<DashMap<Node, BTreeMap<Reference, NodeMonitor>>> =
     Lazy::new(DashMap::new);
 
+/// A collection of monitors installed for every node.
+static NODE_MONITORS_ALL: Lazy<DashMap<Reference, u64>> = Lazy::new(DashMap::new);
+
 /// A collection of node links installed.
 static NODE_LINKS: Lazy<DashMap<Node, BTreeSet<(Pid, u64)>>> = Lazy::new(DashMap::new);
 
 /// A collection of node:vec<msg> pending messages for a node.
-static NODE_PENDING_MESSAGES: Lazy<DashMap<Node, Vec<Frame>>> = Lazy::new(DashMap::new);
+static NODE_PENDING_MESSAGES: Lazy<DashMap<Node, Vec<NodeFrame>>> = Lazy::new(DashMap::new);
 
 /// A secret value that secures the connection between nodes.
 static NODE_COOKIE: Mutex<Option<String>> = Mutex::new(None);
 
+/// The options the local node was started with.
+static NODE_OPTIONS: Mutex<NodeOptions> = Mutex::new(NodeOptions::new());
+
 /// The next id for this node.
 static NODE_ID: AtomicU64 = AtomicU64::new(1);
 
@@ -84,6 +96,12 @@ pub fn node_local_start(name: String, options: NodeOptions) -> Pid {
         panic!("Local node already started!");
     };
 
+    let broadcast_address = options.broadcast_address;
+
+    MessageFormat::set_default(options.message_format);
+
+    *NODE_OPTIONS.lock().unwrap() = options;
+
     let supervisor = Process::spawn(node_local_supervisor(name.clone(), options));
 
     NODE_REGISTRATIONS.insert(
@@ -92,7 +110,7 @@ pub fn node_local_start(name: String, options: NodeOptions) -> Pid {
             Some(supervisor),
             NodeState::Current,
             name,
-            options.broadcast_address,
+            broadcast_address,
         ),
     );
 
@@ -119,8 +137,13 @@ pub fn node_local_stop() {
     NODE_PENDING_MESSAGES.clear();
 }
 
-/// Cleans up distribution information when the local node goes down unexpectedly.
-pub fn node_local_panic() {
+/// Cleans up distribution information when the local node supervised by the given process goes down unexpectedly.
+pub fn node_local_panic(process: Pid) {
+    // The node may have been stopped, and started again with another supervisor.
+    if node_local_process() != Some(process) {
+        return;
+    }
+
     NODE_MAP.clear();
     NODE_REGISTRATIONS.clear();
     NODE_PENDING_MESSAGES.clear();
@@ -134,7 +157,13 @@ pub fn node_local_process() -> Option<Pid> {
 }
 
 /// Sets available worker processes for a node, then, drains any pending messages to them.
-pub fn node_register_workers(node: Node, sender: Pid, receiver: Pid) {
+pub fn node_register_workers(
+    node: Node,
+    sender: Pid,
+    receiver: Pid,
+    frames: flume::Sender<NodeFrame>,
+    stats: Arc<NodeStats>,
+) {
     let Some(entry) = NODE_MAP.get(&node) else {
         return;
     };
@@ -142,47 +171,61 @@ pub fn node_register_workers(node: Node, sender: Pid, receiver: Pid) {
     NODE_REGISTRATIONS.alter(&entry, |_, mut value| {
         value.sender = Some(sender);
         value.receiver = Some(receiver);
+        value.stats = Some(stats);
 
-        let frames = NODE_PENDING_MESSAGES
+        let pending = NODE_PENDING_MESSAGES
             .remove(&node)
             .map(|pending| pending.1)
             .unwrap_or_default();
 
         // We need to pop the pending messages and send them to the sender
         // This way, the order for sent messages is maintained and all future messages go direct to the sender.
-        Process::send(
-            sender,
-            NodeRemoteSenderMessage::SendFrames(Local::new(frames)),
-        );
+        for frame in pending {
+            let _ = frames.send(frame);
+        }
 
+        value.frames = Some(frames);
         value
     });
+
+    node_monitor_all_notify(|reference| ProcessItem::MonitorNodeUp(node.clone(), reference));
 }
 
-/// Gets the send process for a given node.
+/// Sends a frame to the given node, or queues it while the node is connecting.
 pub fn node_send_frame(frame: Frame, id: u64) {
     let Some(registration) = NODE_REGISTRATIONS.get(&id) else {
         return;
     };
 
-    if let Some(sender) = registration.sender {
-        Process::send(
-            sender,
-            NodeRemoteSenderMessage::SendFrame(Local::new(frame)),
-        );
+    // The process a frame originates from keeps its frames in order when control frames are prioritized.
+    let origin = frame
+        .origin()
+        .or_else(|| PROCESS.try_with(|process| process.pid.id()).ok());
+
+    if let Some(frames) = &registration.frames {
+        let _ = frames.send((origin, frame));
     } else if !matches!(registration.state, NodeState::Known) {
-        NODE_PENDING_MESSAGES
-            .entry(Node::from((
-                registration.name.clone(),
-                registration.broadcast_address,
-            )))
-            .or_default()
-            .push(frame);
+        let max_pending_messages = NODE_OPTIONS.lock().unwrap().max_pending_messages;
+
+        let node = Node::from((registration.name.clone(), registration.broadcast_address));
+
+        let mut pending = NODE_PENDING_MESSAGES.entry(node).or_default();
+
+        if pending.len() < max_pending_messages {
+            pending.push((origin, frame));
+        } else {
+            registration
+                .dropped_messages
+                .fetch_add(1, Ordering::Relaxed);
+
+            #[cfg(feature = "tracing")]
+            tracing::error!(node = ?pending.key(), max_pending_messages = ?max_pending_messages, "Dropped message, the pending message queue for the node is full");
+        }
     }
 }
 
 /// Accepts a remote node's connection if one doesn't exist, returns `true` if accepted.
-pub fn node_accept(node: Node, supervisor: Pid) -> bool {
+pub fn node_accept(node: Node, supervisor: Pid, hidden: bool) -> bool {
     let Node::Remote(name, address) = node else {
         panic!("Can't accept a local node!");
     };
@@ -193,10 +236,12 @@ pub fn node_accept(node: Node, supervisor: Pid) -> bool {
         Entry::Vacant(entry) => {
             let next_id = NODE_ID.fetch_add(1, Ordering::Relaxed);
 
-            NODE_REGISTRATIONS.insert(
-                next_id,
-                NodeRegistration::new(Some(supervisor), NodeState::Connected, name, address),
-            );
+            let mut registration =
+                NodeRegistration::new(Some(supervisor), NodeState::Connected, name, address);
+
+            registration.hidden = hidden;
+
+            NODE_REGISTRATIONS.insert(next_id, registration);
 
             entry.insert(next_id);
 
@@ -219,6 +264,8 @@ pub fn node_accept(node: Node, supervisor: Pid) -> bool {
 
                     value.supervisor = Some(supervisor);
                     value.state = NodeState::Connected;
+                    value.hidden = hidden;
+                    value.reconnect_attempts = 0;
                 }
 
                 value
@@ -245,7 +292,7 @@ pub fn node_register(node: Node, connect: bool) -> u64 {
             if connect {
                 NODE_REGISTRATIONS.alter(&id, |_, mut value| {
                     if value.supervisor.is_none() {
-                        value.supervisor = Some(Process::spawn(node_remote_connector(node)));
+                        value.supervisor = Some(Process::spawn(node_remote_connector(node, None)));
                         value.state = NodeState::Pending;
                     }
 
@@ -260,7 +307,7 @@ pub fn node_register(node: Node, connect: bool) -> u64 {
     let next_id = NODE_ID.fetch_add(1, Ordering::Relaxed);
 
     if connect {
-        let supervisor = Process::spawn(node_remote_connector(node));
+        let supervisor = Process::spawn(node_remote_connector(node, None));
 
         NODE_REGISTRATIONS.insert(
             next_id,
@@ -278,78 +325,199 @@ pub fn node_register(node: Node, connect: bool) -> u64 {
     next_id
 }
 
-/// Triggered when a remote node supervisor goes down unexpectedly.
-pub fn node_remote_supervisor_down(node: Node, process: Pid) {
-    let Some(id) = NODE_MAP.get(&node) else {
+/// Sets the reconnect policy for the given remote node, overriding the one the local node was started with.
+pub fn node_set_reconnect_policy(node: Node, policy: ReconnectPolicy) {
+    let Some(id) = NODE_MAP.get(&node).map(|id| *id) else {
         return;
     };
 
     NODE_REGISTRATIONS.alter(&id, |_, mut value| {
-        if value
-            .supervisor
-            .is_some_and(|supervisor| supervisor != process)
-        {
+        value.reconnect_policy = Some(policy);
+        value
+    });
+}
+
+/// Triggered when a remote node supervisor goes down unexpectedly for the given reason.
+pub fn node_remote_supervisor_down(node: Node, process: Pid, exit_reason: ExitReason) {
+    let Some(id) = NODE_MAP.get(&node).map(|id| *id) else {
+        return;
+    };
+
+    let default_policy = NODE_OPTIONS.lock().unwrap().reconnect_policy;
+
+    let mut down: Option<(bool, Option<Duration>, u32)> = None;
+
+    // Only decide the new state under the lock, notifying and reconnecting may touch the registrations again.
+    NODE_REGISTRATIONS.alter(&id, |_, mut value| {
+        if value.supervisor != Some(process) {
             return value;
         }
 
+        let was_connected = value.state == NodeState::Connected;
+
+        let reconnecting = match value.state {
+            NodeState::Connected => true,
+            NodeState::Pending => value.reconnect_attempts > 0,
+            _ => false,
+        };
+
+        let attempt = value.reconnect_attempts + 1;
+
+        let delay = if reconnecting {
+            value
+                .reconnect_policy
+                .unwrap_or(default_policy)
+                .delay(attempt)
+        } else {
+            None
+        };
+
         value.supervisor = None;
         value.sender = None;
         value.receiver = None;
-        value.state = NodeState::Known;
+        value.frames = None;
 
-        if let Some((_, links)) = NODE_LINKS.remove(&node) {
-            for (from, process_id) in links {
-                let process = Pid::local(process_id);
+        if delay.is_some() {
+            // Keep any pending messages until we reconnect, or give up on the node.
+            value.state = NodeState::Pending;
+            value.reconnect_attempts = attempt;
+        } else {
+            NODE_PENDING_MESSAGES.remove(&node);
 
-                link_destroy(process, from);
+            value.state = NodeState::Known;
+            value.reconnect_attempts = 0;
+        }
 
-                process_exit_signal_linked(process, from, ExitReason::from("noconnection"));
-            }
+        down = Some((was_connected, delay, attempt));
+
+        value
+    });
+
+    let Some((was_connected, delay, attempt)) = down else {
+        return;
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(node = ?node, exit_reason = ?exit_reason, "Node went down");
+
+    if was_connected {
+        node_monitor_all_notify(|reference| {
+            ProcessItem::MonitorNodeDown(node.clone(), reference, exit_reason.clone())
+        });
+    }
+
+    node_down(&node, exit_reason);
+
+    let Some(delay) = delay else {
+        return;
+    };
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(node = ?node, delay = ?delay, attempt = ?attempt, "Reconnecting to node");
+
+    let connector = Process::spawn(node_remote_connector(node, Some(delay)));
+
+    let mut started = false;
+
+    NODE_REGISTRATIONS.alter(&id, |_, mut value| {
+        // The node may have been disconnected, or connected again, while we weren't holding the lock.
+        if value.supervisor.is_none()
+            && value.state == NodeState::Pending
+            && value.reconnect_attempts == attempt
+        {
+            value.supervisor = Some(connector);
+
+            started = true;
         }
 
-        if let Some((_, monitors)) = NODE_MONITORS.remove(&node) {
-            for (reference, monitor) in monitors {
-                match monitor {
-                    NodeMonitor::Node(id) => {
-                        process_sender(Pid::local(id)).map(|sender| {
-                            sender.send(ProcessItem::MonitorNodeDown(node.clone(), reference))
-                        });
-                    }
-                    NodeMonitor::ProcessMonitor(id, dest) => {
-                        process_sender(Pid::local(id)).map(|sender| {
-                            sender.send(ProcessItem::MonitorProcessDown(
-                                dest,
-                                reference,
-                                ExitReason::from("noconnection"),
-                            ))
-                        });
-                    }
-                    NodeMonitor::ProcessMonitorCleanup(id) => {
-                        monitor_destroy(Pid::local(id), reference);
+        value
+    });
+
+    if !started {
+        Process::exit(connector, ExitReason::Kill);
+    }
+}
+
+/// Cleans up the links and monitors for a node that went down, notifying monitors with the given reason.
+fn node_down(node: &Node, exit_reason: ExitReason) {
+    if let Some((_, links)) = NODE_LINKS.remove(node) {
+        for (from, process_id) in links {
+            let process = Pid::local(process_id);
+
+            link_destroy(process, from);
+
+            process_exit_signal_linked(process, from, ExitReason::from("noconnection"));
+        }
+    }
+
+    if let Some((_, monitors)) = NODE_MONITORS.remove(node) {
+        for (reference, monitor) in monitors {
+            match monitor {
+                NodeMonitor::Node(id) => {
+                    if let Some(sender) = process_sender(Pid::local(id)) {
+                        let _ = sender.send(ProcessItem::MonitorNodeDown(
+                            node.clone(),
+                            reference,
+                            exit_reason.clone(),
+                        ));
                     }
                 }
-
-                if reference.is_local() {
-                    alias_destroy(reference);
+                NodeMonitor::ProcessMonitor(id, dest) => {
+                    process_sender(Pid::local(id)).map(|sender| {
+                        sender.send(ProcessItem::MonitorProcessDown(
+                            dest,
+                            reference,
+                            ExitReason::from("noconnection"),
+                        ))
+                    });
+                }
+                NodeMonitor::ProcessMonitorCleanup(id) => {
+                    monitor_destroy(Pid::local(id), reference);
                 }
             }
-        }
 
-        NODE_PENDING_MESSAGES.remove(&node);
+            if reference.is_local() {
+                alias_destroy(reference);
+            }
+        }
+    }
+}
 
-        value
-    });
+/// Sends a notification to every process monitoring all nodes.
+fn node_monitor_all_notify<F: Fn(Reference) -> ProcessItem>(item: F) {
+    for entry in NODE_MONITORS_ALL.iter() {
+        if let Some(sender) = process_sender(Pid::local(*entry.value())) {
+            let _ = sender.send(item(*entry.key()));
+        }
+    }
 }
 
-/// Returns the node list excluding the local node.
+/// Returns the node list excluding the local node and any hidden nodes.
 pub fn node_list() -> Vec<Node> {
-    NODE_MAP
+    NODE_REGISTRATIONS
+        .iter()
+        .filter_map(|entry| {
+            if *entry.key() == LOCAL_NODE_ID || entry.hidden {
+                None
+            } else {
+                Some(Node::from((entry.name.clone(), entry.broadcast_address)))
+            }
+        })
+        .collect()
+}
+
+/// Returns the connected, visible peers excluding the given node.
+pub fn node_list_peers(exclude: &Node) -> Vec<(String, SocketAddr)> {
+    NODE_REGISTRATIONS
         .iter()
         .filter_map(|entry| {
-            if matches!(entry.key(), Node::Local) {
+            if entry.hidden
+                || entry.state != NodeState::Connected
+                || *exclude == (entry.name.as_str(), entry.broadcast_address)
+            {
                 None
             } else {
-                Some(entry.key().clone())
+                Some((entry.name.clone(), entry.broadcast_address))
             }
         })
         .collect()
@@ -380,9 +548,23 @@ pub fn node_disconnect(node: Node) {
 
         if let Some(supervisor) = value.supervisor.take() {
             Process::exit(supervisor, ExitReason::Kill);
+
+            let exit_reason = ExitReason::from("disconnect");
+
+            if value.state == NodeState::Connected {
+                node_monitor_all_notify(|reference| {
+                    ProcessItem::MonitorNodeDown(node.clone(), reference, exit_reason.clone())
+                });
+            }
+
+            node_down(&node, exit_reason);
         }
 
+        value.sender = None;
+        value.receiver = None;
+        value.frames = None;
         value.state = NodeState::Known;
+        value.reconnect_attempts = 0;
         value
     });
 }
@@ -411,6 +593,29 @@ pub fn node_lookup_local() -> Option<(String, SocketAddr)> {
         .map(|registration| (registration.name.clone(), registration.broadcast_address))
 }
 
+/// Returns debug information for the given node, if it's registered.
+pub fn node_info(node: Node) -> Option<NodeInfo> {
+    let id = *NODE_MAP.get(&node)?;
+    let registration = NODE_REGISTRATIONS.get(&id)?;
+
+    let mut info = NodeInfo::new(registration.state);
+
+    info.hidden = registration.hidden;
+
+    if let Some(stats) = &registration.stats {
+        info.round_trip_time = stats.round_trip_time();
+        info.last_seen = stats.last_seen();
+        info.bytes_in = stats.bytes_in();
+        info.bytes_out = stats.bytes_out();
+        info.frames_in = stats.frames_in();
+        info.frames_out = stats.frames_out();
+    }
+
+    info.dropped_messages = registration.dropped_messages.load(Ordering::Relaxed);
+
+    Some(info)
+}
+
 /// Looks up the node information for a remote node id.
 pub fn node_lookup_remote(id: u64) -> Option<(String, SocketAddr)> {
     NODE_REGISTRATIONS
@@ -426,6 +631,16 @@ pub fn node_monitor_create(node: Node, reference: Reference, from: Pid) {
         .insert(reference, NodeMonitor::Node(from.id()));
 }
 
+/// Creates a monitor for every node and the given reference from the given process.
+pub fn node_monitor_all_create(reference: Reference, from: Pid) {
+    NODE_MONITORS_ALL.insert(reference, from.id());
+}
+
+/// Destroys a monitor for every node for the given reference.
+pub fn node_monitor_all_destroy(reference: Reference) {
+    NODE_MONITORS_ALL.remove(&reference);
+}
+
 /// Creates a monitor for the given node and reference from the given process for dest.
 pub fn node_process_monitor_create(node: Node, reference: Reference, dest: Dest, from: Pid) {
     NODE_MONITORS
@@ -1,13 +1,15 @@
+use std::io::Error;
+use std::io::ErrorKind;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use serde::Deserialize;
 use serde::Serialize;
 
-use tokio::net::TcpStream;
-
 use tokio_util::codec::Framed;
 
-use futures_util::stream;
 use futures_util::stream::SplitSink;
 use futures_util::stream::SplitStream;
 use futures_util::SinkExt;
@@ -19,8 +21,11 @@ use crate::frame::Hello;
 use crate::frame::LinkDown;
 use crate::frame::MonitorDown;
 use crate::frame::MonitorUpdate;
+use crate::frame::Peers;
 use crate::frame::Ping;
 use crate::frame::Pong;
+use crate::frame::MAX_FRAME_OVERHEAD;
+use crate::frame::PROTOCOL_VERSION;
 
 use crate::link_create;
 use crate::link_destroy;
@@ -29,7 +34,9 @@ use crate::monitor_destroy;
 use crate::node_accept;
 use crate::node_forward_send;
 use crate::node_link_destroy;
+use crate::node_list_peers;
 use crate::node_local_process;
+use crate::node_lookup_local;
 use crate::node_process_link_create;
 use crate::node_process_link_down;
 use crate::node_process_monitor_cleanup;
@@ -47,23 +54,21 @@ use crate::ExitReason;
 use crate::Local;
 use crate::Message;
 use crate::Node;
+use crate::NodeFragments;
+use crate::NodeFrame;
+use crate::NodeLanes;
 use crate::NodeLocalSupervisor;
 use crate::NodeLocalSupervisorMessage;
+use crate::NodeStats;
+use crate::NodeStream;
 use crate::Pid;
 use crate::Process;
 use crate::ProcessItem;
 use crate::Reference;
 
-type Reader = SplitStream<Framed<TcpStream, Codec>>;
-type Writer = SplitSink<Framed<TcpStream, Codec>, Frame>;
-
-#[derive(Serialize, Deserialize)]
-pub enum NodeRemoteSenderMessage {
-    /// Occurs when a new outbound frame is ready to be sent over the socket.
-    SendFrame(Local<Frame>),
-    /// Occurs when a bunch of new outbound frames are ready to be sent over the socket.
-    SendFrames(Local<Vec<Frame>>),
-}
+type Connection = Framed<Box<dyn NodeStream>, Codec>;
+type Reader = SplitStream<Connection>;
+type Writer = SplitSink<Connection, Frame>;
 
 #[derive(Serialize, Deserialize)]
 pub enum NodeRemoteConnectorMessage {
@@ -74,98 +79,182 @@ pub enum NodeRemoteConnectorMessage {
 #[derive(Serialize, Deserialize)]
 enum NodeRemoteSupervisorMessage {
     /// Occurs when the receiver has been sent a ping frame, so we must respond with a pong frame.
-    SendPong,
+    SendPong(u64),
 }
 
 struct NodeRemoteSupervisor {
     node: Node,
     process: Pid,
     local_supervisor: Arc<NodeLocalSupervisor>,
+    stats: Arc<NodeStats>,
+    exit_reason: Mutex<Option<ExitReason>>,
 }
 
 struct NodeRemoteConnector {
     node: Node,
     process: Pid,
+    exit_reason: Mutex<Option<ExitReason>>,
+}
+
+impl NodeRemoteSupervisor {
+    /// Exits the current process, recording the reason the node went down.
+    fn exit<E: Into<ExitReason>>(&self, exit_reason: E) {
+        node_remote_exit(&self.exit_reason, exit_reason.into());
+    }
+}
+
+impl NodeRemoteConnector {
+    /// Exits the current process, recording the reason the node went down.
+    fn exit<E: Into<ExitReason>>(&self, exit_reason: E) {
+        node_remote_exit(&self.exit_reason, exit_reason.into());
+    }
 }
 
 impl Drop for NodeRemoteSupervisor {
     fn drop(&mut self) {
-        node_remote_supervisor_down(self.node.clone(), self.process);
+        let exit_reason = node_remote_exit_reason(&self.exit_reason);
+
+        node_remote_supervisor_down(self.node.clone(), self.process, exit_reason);
     }
 }
 
 impl Drop for NodeRemoteConnector {
     fn drop(&mut self) {
-        node_remote_supervisor_down(self.node.clone(), self.process);
+        let exit_reason = node_remote_exit_reason(&self.exit_reason);
+
+        node_remote_supervisor_down(self.node.clone(), self.process, exit_reason);
+    }
+}
+
+/// Records the first reason the node went down, and exits the current process with it.
+fn node_remote_exit(slot: &Mutex<Option<ExitReason>>, exit_reason: ExitReason) {
+    slot.lock()
+        .unwrap()
+        .get_or_insert_with(|| exit_reason.clone());
+
+    Process::exit(Process::current(), exit_reason);
+}
+
+/// Takes the recorded reason the node went down, defaulting to `noconnection`.
+fn node_remote_exit_reason(slot: &Mutex<Option<ExitReason>>) -> ExitReason {
+    slot.lock()
+        .unwrap()
+        .take()
+        .unwrap_or_else(|| ExitReason::from("noconnection"))
+}
+
+/// Converts a socket error into the reason the node went down.
+fn node_remote_error_reason(error: Error) -> ExitReason {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(error = ?error, "Node connection failed");
+
+    match error.kind() {
+        ErrorKind::InvalidData => ExitReason::from("decode_error"),
+        ErrorKind::InvalidInput => ExitReason::from("encode_error"),
+        _ => ExitReason::from("connection_closed"),
     }
 }
 
-async fn node_remote_sender(mut writer: Writer, supervisor: Arc<NodeRemoteSupervisor>) {
-    let send_timeout = supervisor.local_supervisor.options.heartbeat_interval;
+async fn node_remote_sender(
+    mut writer: Writer,
+    frames: flume::Receiver<NodeFrame>,
+    supervisor: Arc<NodeRemoteSupervisor>,
+) {
+    let options = &supervisor.local_supervisor.options;
+
+    let heartbeat_interval = options.heartbeat_interval;
+    let mut last_heartbeat = Instant::now();
+
+    let mut lanes = NodeLanes::new(options);
 
     loop {
-        let Ok(message) =
-            Process::timeout(send_timeout, Process::receive::<NodeRemoteSenderMessage>()).await
-        else {
-            writer
-                .send(Ping.into())
-                .await
-                .expect("Failed to send a message to the remote node!");
-            continue;
-        };
+        if lanes.is_empty() {
+            let send_timeout = heartbeat_interval.saturating_sub(last_heartbeat.elapsed());
 
-        match message {
-            Message::User(NodeRemoteSenderMessage::SendFrame(frame)) => {
-                writer
-                    .send(frame.into_inner())
-                    .await
-                    .expect("Failed to send a message to the remote node!");
+            match Process::timeout(send_timeout, frames.recv_async()).await {
+                Ok(Ok((origin, frame))) => lanes.push(origin, frame),
+                Ok(Err(_)) => return supervisor.exit("connection_closed"),
+                Err(_) => {}
             }
-            Message::User(NodeRemoteSenderMessage::SendFrames(frames)) => {
-                let mut stream = stream::iter(frames.into_inner().into_iter().map(Ok));
+        }
+
+        // Queue everything that's ready before each write, so that control frames can skip ahead of a backlog.
+        loop {
+            // Heartbeats are checked before each write, so they're sent on time even when the connection never goes idle.
+            if last_heartbeat.elapsed() >= heartbeat_interval {
+                lanes.push(None, Ping::new(NodeStats::timestamp()).into());
 
-                writer
-                    .send_all(&mut stream)
-                    .await
-                    .expect("Failed to send multiple messages to the remote node!");
+                last_heartbeat = Instant::now();
             }
-            _ => unreachable!(),
+
+            while let Ok((origin, frame)) = frames.try_recv() {
+                lanes.push(origin, frame);
+            }
+
+            let Some(frame) = lanes.pop() else {
+                break;
+            };
+
+            if let Err(error) = writer.feed(frame).await {
+                return supervisor.exit(node_remote_error_reason(error));
+            }
+        }
+
+        if let Err(error) = writer.flush().await {
+            return supervisor.exit(node_remote_error_reason(error));
         }
     }
 }
 
 async fn node_remote_receiver(mut reader: Reader, supervisor: Arc<NodeRemoteSupervisor>) {
     let recv_timeout = supervisor.local_supervisor.options.heartbeat_timeout;
+    let max_message_size = supervisor
+        .local_supervisor
+        .options
+        .max_message_size
+        .saturating_add(MAX_FRAME_OVERHEAD);
+
+    let mut fragments = NodeFragments::new(max_message_size);
 
     loop {
-        let message = Process::timeout(recv_timeout, reader.next())
-            .await
-            .expect("Remote node timed out!")
-            .expect("Remote node went down!")
-            .expect("Failed to receive a message from the remote node!");
+        let message = match Process::timeout(recv_timeout, reader.next()).await {
+            Ok(Some(Ok(message))) => message,
+            Ok(Some(Err(error))) => return supervisor.exit(node_remote_error_reason(error)),
+            Ok(None) => return supervisor.exit("connection_closed"),
+            Err(_) => return supervisor.exit("heartbeat_timeout"),
+        };
 
         match message {
-            Frame::Hello(_) => unreachable!("Should never receive hello frame!"),
-            Frame::Ping => {
-                Process::send(supervisor.process, NodeRemoteSupervisorMessage::SendPong);
+            Frame::Hello(_) => return supervisor.exit("handshake_rejected"),
+            Frame::Ping(ping) => {
+                Process::send(
+                    supervisor.process,
+                    NodeRemoteSupervisorMessage::SendPong(ping.timestamp),
+                );
             }
-            Frame::Pong => {
-                // Maybe log this in metrics somewhere!
+            Frame::Pong(pong) => {
+                supervisor.stats.record_round_trip(pong.timestamp);
             }
             Frame::Send(send) => {
                 node_forward_send(send);
             }
+            Frame::Fragment(fragment) => match fragments.push(fragment) {
+                Ok(Some(send)) => node_forward_send(send),
+                Ok(None) => {}
+                Err(exit_reason) => return supervisor.exit(exit_reason),
+            },
             Frame::Monitor(monitor) => {
                 let node = node_register(supervisor.node.clone(), false);
 
-                let from_id = monitor
-                    .from_id
-                    .expect("Must have a from_id for remote monitors!");
-
-                let from = Pid::remote(from_id, node);
                 let reference = Reference::remote(monitor.reference_id, node);
 
                 if monitor.install {
+                    let Some(from_id) = monitor.from_id else {
+                        return supervisor.exit("decode_error");
+                    };
+
+                    let from = Pid::remote(from_id, node);
+
                     if let Some(id) = monitor.process_id {
                         let process = Pid::local(id);
 
@@ -202,10 +291,14 @@ async fn node_remote_receiver(mut reader: Reader, supervisor: Arc<NodeRemoteSupe
                             node_send_frame(monitor_down.into(), node);
                         }
                     } else {
-                        panic!("Must have either a process id or process name for monitor!");
+                        return supervisor.exit("decode_error");
                     };
                 } else {
-                    monitor_destroy(Pid::local(monitor.process_id.unwrap()), reference);
+                    let Some(id) = monitor.process_id else {
+                        return supervisor.exit("decode_error");
+                    };
+
+                    monitor_destroy(Pid::local(id), reference);
 
                     node_process_monitor_destroy(supervisor.node.clone(), reference);
                 }
@@ -273,6 +366,26 @@ async fn node_remote_receiver(mut reader: Reader, supervisor: Arc<NodeRemoteSupe
 
                 process_exit(process, from, exit.exit_reason);
             }
+            Frame::Peers(peers) => {
+                let options = &supervisor.local_supervisor.options;
+
+                if !options.full_mesh || options.hidden {
+                    continue;
+                }
+
+                let local = node_lookup_local();
+
+                for (name, address) in peers.nodes {
+                    if local
+                        .as_ref()
+                        .is_some_and(|local| local.0 == name && local.1 == address)
+                    {
+                        continue;
+                    }
+
+                    node_register(Node::from((name, address)), true);
+                }
+            }
         }
     }
 }
@@ -282,93 +395,156 @@ async fn node_remote_supervisor(
     reader: Reader,
     hello: Hello,
     supervisor: Arc<NodeLocalSupervisor>,
+    stats: Arc<NodeStats>,
 ) {
+    let hidden = hello.hidden;
     let node = Node::from((hello.name, hello.broadcast_address));
 
     let supervisor = Arc::new(NodeRemoteSupervisor {
         node: node.clone(),
         process: Process::current(),
         local_supervisor: supervisor,
+        stats,
+        exit_reason: Mutex::new(None),
     });
 
     Process::link(supervisor.process);
 
-    if !node_accept(node.clone(), Process::current()) {
-        panic!("Not accepting node supervisor!");
+    if !node_accept(node.clone(), Process::current(), hidden) {
+        return supervisor.exit("already_connected");
     }
 
-    let sender = Process::spawn_link(node_remote_sender(writer, supervisor.clone()));
+    let (frames, outbound) = flume::unbounded();
+
+    let sender = Process::spawn_link(node_remote_sender(writer, outbound, supervisor.clone()));
     let receiver = Process::spawn_link(node_remote_receiver(reader, supervisor.clone()));
 
-    node_register_workers(node, sender, receiver);
+    let options = &supervisor.local_supervisor.options;
+
+    if options.full_mesh && !options.hidden && !hidden {
+        let peers = Peers::new(node_list_peers(&node));
+
+        let _ = frames.send((None, peers.into()));
+    }
+
+    node_register_workers(
+        node,
+        sender,
+        receiver,
+        frames.clone(),
+        supervisor.stats.clone(),
+    );
 
     loop {
         let message = Process::receive::<NodeRemoteSupervisorMessage>().await;
 
         match message {
-            Message::User(NodeRemoteSupervisorMessage::SendPong) => {
-                Process::send(
-                    sender,
-                    NodeRemoteSenderMessage::SendFrame(Local::new(Pong.into())),
-                );
+            Message::User(NodeRemoteSupervisorMessage::SendPong(timestamp)) => {
+                let _ = frames.send((None, Pong::new(timestamp).into()));
             }
             _ => unreachable!(),
         }
     }
 }
 
-pub async fn node_remote_accepter(socket: TcpStream, supervisor: Arc<NodeLocalSupervisor>) {
-    if let Err(error) = socket.set_nodelay(true) {
-        #[cfg(feature = "tracing")]
-        tracing::warn!(error = ?error, "Failed to set TCP_NODELAY on socket");
-
-        #[cfg(not(feature = "tracing"))]
-        let _ = error;
-    }
-
-    let framed = Framed::new(socket, Codec::new());
-    let (mut writer, mut reader) = framed.split();
-
+/// Exchanges hello frames with the remote node, returning the validated remote hello.
+///
+/// Once validated, the connection compresses frames with the compression both nodes support.
+async fn node_remote_handshake(
+    connection: &mut Connection,
+    supervisor: &NodeLocalSupervisor,
+) -> Result<Hello, ExitReason> {
     let hello = Hello::new(
         supervisor.name.clone(),
         supervisor.options.broadcast_address,
+        supervisor.options.hidden,
     );
 
     let handshake_timeout = supervisor.options.handshake_timeout;
 
-    Process::timeout(handshake_timeout, writer.send(hello.into()))
-        .await
-        .expect("Timed out while sending hello handshake packet!")
-        .expect("Failed to send hello handshake packet!");
+    match Process::timeout(handshake_timeout, connection.send(hello.into())).await {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => return Err(node_remote_error_reason(error)),
+        Err(_) => return Err(ExitReason::from("handshake_timeout")),
+    }
 
-    let frame = Process::timeout(handshake_timeout, reader.next())
-        .await
-        .expect("Timed out while receiving hello handshake packet!")
-        .unwrap()
-        .expect("Failed to receive hello handshake packet!");
-
-    if let Frame::Hello(mut hello) = frame {
-        if hello.validate() {
-            Process::spawn(node_remote_supervisor(writer, reader, hello, supervisor));
-            return;
-        } else {
-            panic!("Node handshake failed validation!");
+    let frame = match Process::timeout(handshake_timeout, connection.next()).await {
+        Ok(Some(Ok(frame))) => frame,
+        // A hello we can't decode comes from a node speaking another version of the protocol.
+        Ok(Some(Err(error))) if error.kind() == ErrorKind::InvalidData => {
+            return Err(ExitReason::from("handshake_rejected"))
         }
+        Ok(Some(Err(error))) => return Err(node_remote_error_reason(error)),
+        Ok(None) => return Err(ExitReason::from("connection_closed")),
+        Err(_) => return Err(ExitReason::from("handshake_timeout")),
+    };
+
+    let Frame::Hello(mut hello) = frame else {
+        return Err(ExitReason::from("handshake_rejected"));
+    };
+
+    if hello.version != PROTOCOL_VERSION {
+        return Err(ExitReason::from("unsupported_protocol_version"));
+    }
+
+    if !hello.validate() {
+        return Err(ExitReason::from("handshake_rejected"));
     }
 
-    panic!("Received incorrect frame for node handshake!");
+    let options = &supervisor.options;
+
+    if !hello.message_formats.contains(&options.message_format.id()) {
+        return Err(ExitReason::from("unsupported_message_format"));
+    }
+
+    connection.codec_mut().set_compression(
+        options.compression.negotiate(&hello.compression),
+        options.compression_threshold,
+    );
+
+    Ok(hello)
+}
+
+pub async fn node_remote_accepter(
+    socket: Box<dyn NodeStream>,
+    supervisor: Arc<NodeLocalSupervisor>,
+) {
+    let stats = Arc::new(NodeStats::new());
+
+    let mut connection = Framed::new(
+        socket,
+        Codec::new(stats.clone(), supervisor.options.max_message_size),
+    );
+
+    let hello = match node_remote_handshake(&mut connection, &supervisor).await {
+        Ok(hello) => hello,
+        Err(exit_reason) => return Process::exit(Process::current(), exit_reason),
+    };
+
+    let (writer, reader) = connection.split();
+
+    Process::spawn(node_remote_supervisor(
+        writer, reader, hello, supervisor, stats,
+    ));
 }
 
-pub async fn node_remote_connector(node: Node) {
+pub async fn node_remote_connector(node: Node, delay: Option<Duration>) {
     let connector = NodeRemoteConnector {
         node: node.clone(),
         process: Process::current(),
+        exit_reason: Mutex::new(None),
     };
 
-    let local = node_local_process().expect("Local node not started!");
+    let Some(local) = node_local_process() else {
+        return connector.exit("connection_failed");
+    };
 
     Process::link(local);
 
+    if let Some(delay) = delay {
+        Process::sleep(delay).await;
+    }
+
     Process::send(
         local,
         NodeLocalSupervisorMessage::RequestLocalSupervisor(Process::current()),
@@ -377,63 +553,56 @@ pub async fn node_remote_connector(node: Node) {
     let Message::User(NodeRemoteConnectorMessage::LocalNodeSupervisor(supervisor)) =
         Process::receive::<NodeRemoteConnectorMessage>().await
     else {
-        panic!("Received unexpected message in remote connector!");
+        return connector.exit("connection_failed");
     };
 
-    let address = node
-        .address()
-        .expect("Must have an address for a remote node!");
+    let Some(address) = node.address() else {
+        return connector.exit("connection_failed");
+    };
 
     let handshake_timeout = supervisor.options.handshake_timeout;
 
-    let socket = Process::timeout(handshake_timeout, TcpStream::connect(address))
-        .await
-        .expect("Timed out while connecting to the node!")
-        .expect("Failed to connect to the node!");
-
-    if let Err(error) = socket.set_nodelay(true) {
-        #[cfg(feature = "tracing")]
-        tracing::warn!(error = ?error, "Failed to set TCP_NODELAY on socket");
-
-        #[cfg(not(feature = "tracing"))]
-        let _ = error;
-    }
+    let socket = match Process::timeout(
+        handshake_timeout,
+        supervisor.options.node_transport().connect(address),
+    )
+    .await
+    {
+        Ok(Ok(socket)) => socket,
+        Ok(Err(error)) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(node = ?node, error = ?error, "Failed to connect to node");
+
+            #[cfg(not(feature = "tracing"))]
+            let _ = error;
+
+            return connector.exit("connection_failed");
+        }
+        Err(_) => return connector.exit("connection_failed"),
+    };
 
-    let framed = Framed::new(socket, Codec::new());
-    let (mut writer, mut reader) = framed.split();
+    let stats = Arc::new(NodeStats::new());
 
-    let hello = Hello::new(
-        supervisor.name.clone(),
-        supervisor.options.broadcast_address,
+    let mut connection = Framed::new(
+        socket,
+        Codec::new(stats.clone(), supervisor.options.max_message_size),
     );
 
-    Process::timeout(handshake_timeout, writer.send(hello.into()))
-        .await
-        .expect("Timed out while sending hello handshake packet!")
-        .expect("Failed to send hello handshake packet!");
+    let hello = match node_remote_handshake(&mut connection, &supervisor).await {
+        Ok(hello) => hello,
+        Err(exit_reason) => return connector.exit(exit_reason),
+    };
 
-    let frame = Process::timeout(handshake_timeout, reader.next())
-        .await
-        .expect("Timed out while receiving hello handshake packet!")
-        .unwrap()
-        .expect("Failed to receive hello handshake packet!");
-
-    if let Frame::Hello(mut hello) = frame {
-        let matches = node == (hello.name.as_str(), hello.broadcast_address);
-
-        if hello.validate() && matches {
-            std::mem::forget(connector);
-            return node_remote_supervisor(writer, reader, hello, supervisor.into_inner()).await;
-        } else if !matches {
-            panic!(
-                "Node was not the expected node: {:?} (wanted) {:?} (received).",
-                node,
-                Node::from((hello.name, hello.broadcast_address))
-            );
-        } else {
-            panic!("Node handshake failed validation!");
-        }
+    if node != (hello.name.as_str(), hello.broadcast_address) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(expected = ?node, received = ?Node::from((hello.name, hello.broadcast_address)), "Node was not the expected node");
+
+        return connector.exit("handshake_rejected");
     }
 
-    panic!("Received incorrect frame for node handshake!");
+    std::mem::forget(connector);
+
+    let (writer, reader) = connection.split();
+
+    node_remote_supervisor(writer, reader, hello, supervisor.into_inner(), stats).await
 }
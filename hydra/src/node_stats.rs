@@ -0,0 +1,117 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use once_cell::sync::Lazy;
+
+/// The instant all heartbeat timestamps are relative to.
+static NODE_STATS_EPOCH: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Represents an unknown value for a stat.
+const UNKNOWN: u64 = u64::MAX;
+
+/// Realtime statistics for a remote node connection.
+pub struct NodeStats {
+    /// The last measured round trip time in microseconds.
+    round_trip_time: AtomicU64,
+    /// The last time a frame was received in milliseconds since the unix epoch.
+    last_seen: AtomicU64,
+    /// The number of bytes received.
+    bytes_in: AtomicU64,
+    /// The number of bytes sent.
+    bytes_out: AtomicU64,
+    /// The number of frames received.
+    frames_in: AtomicU64,
+    /// The number of frames sent.
+    frames_out: AtomicU64,
+}
+
+impl NodeStats {
+    /// Constructs a new instance of [NodeStats].
+    pub const fn new() -> Self {
+        Self {
+            round_trip_time: AtomicU64::new(UNKNOWN),
+            last_seen: AtomicU64::new(UNKNOWN),
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            frames_in: AtomicU64::new(0),
+            frames_out: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a monotonic timestamp in microseconds used for heartbeats.
+    pub fn timestamp() -> u64 {
+        NODE_STATS_EPOCH.elapsed().as_micros() as u64
+    }
+
+    /// Records a frame of the given size that was received.
+    pub fn record_in(&self, size: usize) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        self.last_seen.store(now, Ordering::Relaxed);
+        self.bytes_in.fetch_add(size as u64, Ordering::Relaxed);
+        self.frames_in.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a frame of the given size that was sent.
+    pub fn record_out(&self, size: usize) {
+        self.bytes_out.fetch_add(size as u64, Ordering::Relaxed);
+        self.frames_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a round trip time from the given heartbeat timestamp.
+    pub fn record_round_trip(&self, timestamp: u64) {
+        let round_trip_time = Self::timestamp().saturating_sub(timestamp);
+
+        self.round_trip_time
+            .store(round_trip_time, Ordering::Relaxed);
+    }
+
+    /// Returns the last measured round trip time.
+    pub fn round_trip_time(&self) -> Option<Duration> {
+        match self.round_trip_time.load(Ordering::Relaxed) {
+            UNKNOWN => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Returns the last time a frame was received.
+    pub fn last_seen(&self) -> Option<SystemTime> {
+        match self.last_seen.load(Ordering::Relaxed) {
+            UNKNOWN => None,
+            millis => Some(UNIX_EPOCH + Duration::from_millis(millis)),
+        }
+    }
+
+    /// Returns the number of bytes received.
+    pub fn bytes_in(&self) -> u64 {
+        self.bytes_in.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes sent.
+    pub fn bytes_out(&self) -> u64 {
+        self.bytes_out.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of frames received.
+    pub fn frames_in(&self) -> u64 {
+        self.frames_in.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of frames sent.
+    pub fn frames_out(&self) -> u64 {
+        self.frames_out.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for NodeStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
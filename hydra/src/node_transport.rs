@@ -0,0 +1,29 @@
+use std::io;
+use std::net::SocketAddr;
+
+use futures_util::future::BoxFuture;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+
+/// A bidirectional byte stream that carries frames between two nodes.
+pub trait NodeStream: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<T> NodeStream for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+/// Accepts incoming connections from remote nodes.
+pub trait NodeListener: Send + 'static {
+    /// Waits for the next incoming connection.
+    fn accept(&mut self) -> BoxFuture<'_, io::Result<Box<dyn NodeStream>>>;
+}
+
+/// A transport used to listen for, and connect to, remote nodes.
+///
+/// Nodes are always identified by their broadcast address, the transport decides how that address is reached.
+pub trait NodeTransport: Send + Sync + 'static {
+    /// Starts listening for incoming connections on the given address.
+    fn listen(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeListener>>>;
+
+    /// Connects to the node at the given address.
+    fn connect(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeStream>>>;
+}
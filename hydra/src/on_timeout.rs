@@ -0,0 +1,9 @@
+/// Controls what happens when a task of an async stream times out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnTimeout {
+    /// Kills every running task, and exits the process consuming the stream with the `timeout` reason.
+    #[default]
+    Exit,
+    /// Kills the task that timed out, and emits [crate::TaskError::Timeout] in it's place.
+    KillTask,
+}
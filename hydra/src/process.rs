@@ -1,15 +1,19 @@
 use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::future::Future;
 use std::panic::AssertUnwindSafe;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use flume::Receiver;
 use flume::Sender;
 
+use tokio::sync::oneshot;
+
 use crate::alias_create;
 use crate::alias_destroy;
 use crate::alias_destroy_all;
@@ -26,6 +30,7 @@ use crate::monitor_install;
 use crate::monitor_process_down;
 use crate::node_process_send_exit;
 use crate::process_alive;
+use crate::process_broadcast;
 use crate::process_destroy_timer;
 use crate::process_drop;
 use crate::process_exit;
@@ -36,12 +41,15 @@ use crate::process_list;
 use crate::process_name_list;
 use crate::process_name_lookup;
 use crate::process_name_remove;
+use crate::process_name_reserve;
 use crate::process_read_timer;
 use crate::process_register;
 use crate::process_register_timer;
 use crate::process_send;
 use crate::process_set_exit_reason;
 use crate::process_set_flags;
+use crate::process_set_label;
+use crate::process_set_metadata;
 use crate::process_unregister;
 use crate::ArgumentError;
 use crate::AsyncCatchUnwind;
@@ -50,14 +58,18 @@ use crate::Dests;
 use crate::ExitReason;
 use crate::Message;
 use crate::Pid;
+use crate::ProcessDictionary;
 use crate::ProcessFlags;
 use crate::ProcessInfo;
 use crate::ProcessItem;
 use crate::ProcessMonitor;
 use crate::ProcessReceiver;
 use crate::ProcessRegistration;
+use crate::ProcessStats;
+use crate::ProcessStatsFuture;
 use crate::Receivable;
 use crate::Reference;
+use crate::SpawnOptions;
 use crate::Timeout;
 
 /// The send type for a process.
@@ -79,6 +91,10 @@ pub struct Process {
     pub(crate) aliases: RefCell<BTreeSet<u64>>,
     /// A collection of process monitor references.
     pub(crate) monitors: RefCell<BTreeMap<Reference, ProcessMonitor>>,
+    /// The runtime statistics for this process.
+    pub(crate) stats: Arc<ProcessStats>,
+    /// The typed values stored by this process.
+    pub(crate) dictionary: RefCell<ProcessDictionary>,
 }
 
 tokio::task_local! {
@@ -87,8 +103,14 @@ tokio::task_local! {
 }
 
 impl Process {
-    /// Constructs a new [Process] from the given [Pid] and channels.
-    pub(crate) fn new(pid: Pid, sender: ProcessSend, receiver: ProcessReceive) -> Self {
+    /// Constructs a new [Process] from the given [Pid], channels, stats, and dictionary.
+    pub(crate) fn new(
+        pid: Pid,
+        sender: ProcessSend,
+        receiver: ProcessReceive,
+        stats: Arc<ProcessStats>,
+        dictionary: ProcessDictionary,
+    ) -> Self {
         Self {
             pid,
             sender,
@@ -96,6 +118,8 @@ impl Process {
             items: RefCell::new(Vec::new()),
             aliases: RefCell::new(BTreeSet::new()),
             monitors: RefCell::new(BTreeMap::new()),
+            stats,
+            dictionary: RefCell::new(dictionary),
         }
     }
 
@@ -153,7 +177,30 @@ impl Process {
     /// Process::send(&[pid1, pid2], "hello world!");
     /// ```
     pub fn send<D: Into<Dests>, M: Receivable>(dests: D, message: M) {
-        process_send(dests.into(), message);
+        let dests = dests.into();
+
+        record_sent(&dests);
+
+        process_send(dests, message);
+    }
+
+    /// Sends a message to every destination in `dests`.
+    ///
+    /// Local processes receive a clone of the message, while remote nodes receive the message serialized once and shared by every process on that node.
+    ///
+    /// ## Example:
+    /// Broadcasting a large payload wrapped in an [std::sync::Arc] only clones the pointer for local processes:
+    /// ```ignore
+    /// let payload = Arc::new(vec![0u8; 1024 * 1024]);
+    ///
+    /// Process::broadcast(&[pid1, pid2, pid3], payload);
+    /// ```
+    pub fn broadcast<D: Into<Dests>, M: Receivable + Clone>(dests: D, message: M) {
+        let dests = dests.into();
+
+        record_sent(&dests);
+
+        process_broadcast(dests, message);
     }
 
     /// Sends a message to `dests` after the given `duration`.
@@ -221,9 +268,9 @@ impl Process {
         T: Future<Output = ()> + Send + 'static,
         T::Output: Send + 'static,
     {
-        match spawn_internal(function, false, false) {
-            SpawnResult::Pid(pid) => pid,
-            SpawnResult::PidMonitor(_, _) => unreachable!(),
+        match spawn_internal(function, SpawnOptions::new()) {
+            Ok((pid, _)) => pid,
+            Err(_) => unreachable!(),
         }
     }
 
@@ -233,9 +280,9 @@ impl Process {
         T: Future<Output = ()> + Send + 'static,
         T::Output: Send + 'static,
     {
-        match spawn_internal(function, true, false) {
-            SpawnResult::Pid(pid) => pid,
-            SpawnResult::PidMonitor(_, _) => unreachable!(),
+        match spawn_internal(function, SpawnOptions::new().link(true)) {
+            Ok((pid, _)) => pid,
+            Err(_) => unreachable!(),
         }
     }
 
@@ -245,12 +292,28 @@ impl Process {
         T: Future<Output = ()> + Send + 'static,
         T::Output: Send + 'static,
     {
-        match spawn_internal(function, false, true) {
-            SpawnResult::Pid(_) => unreachable!(),
-            SpawnResult::PidMonitor(pid, monitor) => (pid, monitor),
+        match spawn_internal(function, SpawnOptions::new().monitor(true)) {
+            Ok((pid, Some(monitor))) => (pid, monitor),
+            _ => unreachable!(),
         }
     }
 
+    /// Spawns the given `function` as a process with the given `options`, and returns the new [Pid], and a [Reference] if a monitor was requested.
+    ///
+    /// The name, link, monitor, flags, and label are all in place before the process is first polled.
+    ///
+    /// If the name is already registered, the process is not spawned and an error is returned.
+    pub fn spawn_opt<T>(
+        function: T,
+        options: SpawnOptions,
+    ) -> Result<(Pid, Option<Reference>), ArgumentError>
+    where
+        T: Future<Output = ()> + Send + 'static,
+        T::Output: Send + 'static,
+    {
+        spawn_internal(function, options)
+    }
+
     /// Returns true if the given [Pid] is alive on the local node.
     #[must_use]
     pub fn alive(pid: Pid) -> bool {
@@ -384,6 +447,44 @@ impl Process {
         process_set_flags(Self::current(), flags)
     }
 
+    /// Sets a label that describes the current process in [ProcessInfo].
+    pub fn set_label<T: Into<String>>(label: T) {
+        process_set_label(Self::current(), Some(label.into()))
+    }
+
+    /// Sets a metadata value that describes the current process in [ProcessInfo].
+    pub fn set_metadata<K: Into<String>, V: Into<String>>(key: K, value: V) {
+        process_set_metadata(Self::current(), key.into(), Some(value.into()))
+    }
+
+    /// Removes a metadata value from the current process.
+    pub fn remove_metadata<K: Into<String>>(key: K) {
+        process_set_metadata(Self::current(), key.into(), None)
+    }
+
+    /// Stores a value of the given type in the current process dictionary, returning the previous value.
+    pub fn put<T: Send + 'static>(value: T) -> Option<T> {
+        PROCESS.with(|process| process.dictionary.borrow_mut().put(value))
+    }
+
+    /// Stores a value of the given type in the current process dictionary, returning the previous value.
+    ///
+    /// Processes spawned by the current process start with a clone of the value.
+    pub fn put_inherited<T: Clone + Send + 'static>(value: T) -> Option<T> {
+        PROCESS.with(|process| process.dictionary.borrow_mut().put_inherited(value))
+    }
+
+    /// Returns a clone of the value of the given type from the current process dictionary.
+    #[must_use]
+    pub fn get<T: Clone + 'static>() -> Option<T> {
+        PROCESS.with(|process| process.dictionary.borrow().get())
+    }
+
+    /// Removes the value of the given type from the current process dictionary, returning it.
+    pub fn delete<T: 'static>() -> Option<T> {
+        PROCESS.with(|process| process.dictionary.borrow_mut().delete())
+    }
+
     /// Sends an exit signal with the given reason to [Pid].
     pub fn exit<E: Into<ExitReason>>(pid: Pid, exit_reason: E) {
         let exit_reason = exit_reason.into();
@@ -412,6 +513,26 @@ impl Process {
             info
         })
     }
+
+    /// Fetches debug information for up to `count` local processes, sorted by the time spent polling them, busiest first.
+    #[must_use]
+    pub fn top(count: usize) -> Vec<(Pid, ProcessInfo)> {
+        let mut top: Vec<(Pid, ProcessInfo)> = process_list()
+            .into_iter()
+            .filter_map(|pid| process_info(pid).map(|info| (pid, info)))
+            .collect();
+
+        top.sort_unstable_by_key(|(_, info)| Reverse(info.busy_time));
+        top.truncate(count);
+
+        for (pid, info) in &mut top {
+            link_fill_info(*pid, info);
+
+            monitor_fill_info(*pid, info);
+        }
+
+        top
+    }
 }
 
 impl Drop for Process {
@@ -433,32 +554,54 @@ impl Drop for Process {
     }
 }
 
-/// Internal spawn result.
-enum SpawnResult {
-    Pid(Pid),
-    PidMonitor(Pid, Reference),
+/// Records the messages sent to the given destinations by the current process, if any.
+fn record_sent(dests: &Dests) {
+    let _ = PROCESS.try_with(|process| process.stats.record_sent(dests.len()));
 }
 
 /// The next process id to allocate if free.
 static ID: AtomicU64 = AtomicU64::new(1);
 
 /// Internal spawn utility.
-fn spawn_internal<T>(function: T, link: bool, monitor: bool) -> SpawnResult
+fn spawn_internal<T>(
+    function: T,
+    options: SpawnOptions,
+) -> Result<(Pid, Option<Reference>), ArgumentError>
 where
     T: Future<Output = ()> + Send + 'static,
     T::Output: Send + 'static,
 {
     let next_id = ID.fetch_add(1, Ordering::Relaxed);
 
+    let pid = Pid::local(next_id);
+
+    // If a name was requested, reserve it before anything else so that the process isn't spawned if it's taken.
+    if let Some(name) = &options.name {
+        if !process_name_reserve(name.clone(), next_id) {
+            return Err(ArgumentError::from(format!(
+                "Name {:?} registered to another process!",
+                name
+            )));
+        }
+    }
+
     let (tx, rx) = flume::unbounded();
 
-    let pid = Pid::local(next_id);
-    let process = Process::new(pid, tx.clone(), rx);
+    let stats = Arc::new(ProcessStats::new(
+        std::mem::size_of_val(&function) + std::mem::size_of::<Process>(),
+    ));
+
+    // Inherited values are cloned from the calling process, if any.
+    let dictionary = PROCESS
+        .try_with(|process| process.dictionary.borrow().inherit())
+        .unwrap_or_default();
 
-    let mut result = SpawnResult::Pid(pid);
+    let process = Process::new(pid, tx.clone(), rx, stats.clone(), dictionary);
+
+    let mut result = (pid, None);
 
     // If a link was requested, insert it before spawning the process.
-    if link {
+    if options.link {
         let current = Process::current();
 
         link_create(pid, current, true);
@@ -466,7 +609,7 @@ where
     }
 
     // If a monitor was requested, insert it before spawning the process.
-    if monitor {
+    if options.monitor {
         let monitor = Reference::new();
 
         PROCESS.with(|process| {
@@ -478,18 +621,31 @@ where
 
         monitor_create(pid, monitor, Process::current(), Some(pid.into()));
 
-        result = SpawnResult::PidMonitor(pid, monitor);
+        result.1 = Some(monitor);
     }
 
-    // Spawn the process with the newly created process object in scope.
+    let function = ProcessStatsFuture::new(function, stats.clone());
+
+    let (registered_tx, registered_rx) = oneshot::channel::<()>();
+
+    // Spawn the process with the newly created process object in scope, it waits until it's registered to run.
     let handle = tokio::spawn(PROCESS.scope(process, async move {
+        let _ = registered_rx.await;
+
         if let Err(e) = AsyncCatchUnwind::new(AssertUnwindSafe(function)).await {
             process_set_exit_reason(Process::current(), e.into());
         }
     }));
 
-    // Register the process under it's new id.
-    process_insert(next_id, ProcessRegistration::new(handle, tx));
+    let mut registration = ProcessRegistration::new(handle, tx, stats);
+
+    registration.name = options.name;
+    registration.label = options.label;
+    registration.flags = options.flags;
+
+    process_insert(next_id, registration);
+
+    let _ = registered_tx.send(());
 
-    result
+    Ok(result)
 }
@@ -0,0 +1,96 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+/// A value stored in the process dictionary.
+type DictionaryValue = Box<dyn Any + Send>;
+
+/// Clones a dictionary value for a child process.
+type DictionaryClone = fn(&DictionaryValue) -> DictionaryValue;
+
+/// An entry in the process dictionary.
+struct DictionaryEntry {
+    value: DictionaryValue,
+    inherit: Option<DictionaryClone>,
+}
+
+/// Typed per-process storage, keyed by the type of the value.
+#[derive(Default)]
+pub struct ProcessDictionary {
+    entries: BTreeMap<TypeId, DictionaryEntry>,
+}
+
+impl ProcessDictionary {
+    /// Stores the given value, returning the previous value of the same type.
+    pub fn put<T: Send + 'static>(&mut self, value: T) -> Option<T> {
+        self.insert(value, None)
+    }
+
+    /// Stores the given value, which is cloned into processes spawned by this process, returning the previous value of the same type.
+    pub fn put_inherited<T: Clone + Send + 'static>(&mut self, value: T) -> Option<T> {
+        self.insert(value, Some(clone_value::<T>))
+    }
+
+    /// Returns a clone of the value of the given type.
+    pub fn get<T: Clone + 'static>(&self) -> Option<T> {
+        self.entries
+            .get(&TypeId::of::<T>())
+            .and_then(|entry| entry.value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Removes the value of the given type, returning it.
+    pub fn delete<T: 'static>(&mut self) -> Option<T> {
+        self.entries
+            .remove(&TypeId::of::<T>())
+            .and_then(|entry| entry.value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    /// Constructs a new [ProcessDictionary] for a child process with a clone of every inherited value.
+    pub fn inherit(&self) -> Self {
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|(key, entry)| {
+                let inherit = entry.inherit?;
+
+                Some((
+                    *key,
+                    DictionaryEntry {
+                        value: inherit(&entry.value),
+                        inherit: Some(inherit),
+                    },
+                ))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Stores the given value, and how to clone it for a child process.
+    fn insert<T: Send + 'static>(
+        &mut self,
+        value: T,
+        inherit: Option<DictionaryClone>,
+    ) -> Option<T> {
+        self.entries
+            .insert(
+                TypeId::of::<T>(),
+                DictionaryEntry {
+                    value: Box::new(value),
+                    inherit,
+                },
+            )
+            .and_then(|entry| entry.value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+}
+
+/// Clones a dictionary value of the given type.
+fn clone_value<T: Clone + Send + 'static>(value: &DictionaryValue) -> DictionaryValue {
+    match value.downcast_ref::<T>() {
+        Some(value) => Box::new(value.clone()),
+        None => unreachable!(),
+    }
+}
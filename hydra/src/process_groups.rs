@@ -0,0 +1,447 @@
+use std::collections::BTreeMap;
+
+use dashmap::DashMap;
+
+use once_cell::sync::Lazy;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::CallError;
+use crate::ChildSpec;
+use crate::ChildType;
+use crate::Dest;
+use crate::ExitReason;
+use crate::From;
+use crate::GenServer;
+use crate::GenServerOptions;
+use crate::Message;
+use crate::Node;
+use crate::Pid;
+use crate::Process;
+use crate::Reference;
+use crate::SystemMessage;
+
+/// A local view of the members of every group, by scope, including remote members.
+static PROCESS_GROUPS: Lazy<DashMap<String, DashMap<String, Vec<Pid>>>> = Lazy::new(DashMap::new);
+
+/// A process groups message.
+#[doc(hidden)]
+#[derive(Serialize, Deserialize)]
+pub enum ProcessGroupsMessage {
+    Join(String, Pid),
+    JoinSuccess,
+    Leave(String, Pid),
+    LeaveSuccess,
+    LeaveError(ProcessGroupsError),
+    Discover(Pid),
+    Sync(Pid, Vec<(String, Vec<Pid>)>),
+    PeerJoin(Pid, String, Pid),
+    PeerLeave(Pid, String, Pid),
+}
+
+/// Errors for [ProcessGroups] calls.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ProcessGroupsError {
+    /// A call to the [ProcessGroups] server has failed.
+    CallError(CallError),
+    /// Only local processes can join or leave a group.
+    NotLocal,
+    /// The process is not a member of the group.
+    NotJoined,
+}
+
+/// Provides named groups of processes, where membership is synced across every connected node that runs the same scope.
+///
+/// Members leave their groups automatically when they exit.
+pub struct ProcessGroups {
+    scope: String,
+    running: bool,
+    local: BTreeMap<Pid, (Reference, Vec<String>)>,
+    peers: BTreeMap<Pid, (Reference, BTreeMap<String, Vec<Pid>>)>,
+}
+
+impl ProcessGroups {
+    /// Constructs a new [ProcessGroups] scope with the given name.
+    ///
+    /// Scopes sync with the scope of the same name on every connected node.
+    #[must_use]
+    pub fn new<T: Into<String>>(scope: T) -> Self {
+        Self {
+            scope: scope.into(),
+            running: false,
+            local: BTreeMap::new(),
+            peers: BTreeMap::new(),
+        }
+    }
+
+    /// Joins the local process `pid` to the `group` in the given `scope`.
+    ///
+    /// Joining a group the process is already a member of does nothing.
+    pub async fn join<S: Into<String>, G: Into<String>>(
+        scope: S,
+        group: G,
+        pid: Pid,
+    ) -> Result<(), ProcessGroupsError> {
+        use ProcessGroupsMessage::*;
+
+        if pid.is_remote() {
+            return Err(ProcessGroupsError::NotLocal);
+        }
+
+        match ProcessGroups::call(scope.into(), Join(group.into(), pid), None).await? {
+            JoinSuccess => Ok(()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Removes the local process `pid` from the `group` in the given `scope`.
+    pub async fn leave<S: Into<String>, G: Into<String>>(
+        scope: S,
+        group: G,
+        pid: Pid,
+    ) -> Result<(), ProcessGroupsError> {
+        use ProcessGroupsMessage::*;
+
+        if pid.is_remote() {
+            return Err(ProcessGroupsError::NotLocal);
+        }
+
+        match ProcessGroups::call(scope.into(), Leave(group.into(), pid), None).await? {
+            LeaveSuccess => Ok(()),
+            LeaveError(error) => Err(error),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns every member of the `group` in the given `scope`, on every node.
+    ///
+    /// This queries the local view of the scope without hitting the scope process.
+    pub fn get_members<S: AsRef<str>, G: AsRef<str>>(scope: S, group: G) -> Vec<Pid> {
+        PROCESS_GROUPS
+            .get(scope.as_ref())
+            .and_then(|scope| scope.get(group.as_ref()).map(|members| members.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Returns the members of the `group` in the given `scope` that run on the local node.
+    pub fn get_local_members<S: AsRef<str>, G: AsRef<str>>(scope: S, group: G) -> Vec<Pid> {
+        let mut members = Self::get_members(scope, group);
+
+        members.retain(|member| member.is_local());
+        members
+    }
+
+    /// Returns the name of every group with at least one member in the given `scope`.
+    pub fn which_groups<S: AsRef<str>>(scope: S) -> Vec<String> {
+        PROCESS_GROUPS
+            .get(scope.as_ref())
+            .map(|scope| scope.iter().map(|entry| entry.key().clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Create a scope process not linked to a supervision tree.
+    pub async fn start(self) -> Result<Pid, ExitReason> {
+        let options = GenServerOptions::new().name(self.scope.clone());
+
+        GenServer::start(self, options).await
+    }
+
+    /// Creates a scope process as part of a supervision tree.
+    pub async fn start_link(self) -> Result<Pid, ExitReason> {
+        let options = GenServerOptions::new().name(self.scope.clone());
+
+        GenServer::start_link(self, options).await
+    }
+
+    /// Builds a child specification for this [ProcessGroups] scope process.
+    pub fn child_spec(mut self) -> ChildSpec {
+        let scope = std::mem::take(&mut self.scope);
+
+        ChildSpec::new("ProcessGroups")
+            .start(move || ProcessGroups::new(scope.clone()).start_link())
+            .child_type(ChildType::Worker)
+    }
+
+    /// Joins a local process to a group, and notifies every peer.
+    fn join_local(&mut self, group: String, pid: Pid) {
+        let (_, groups) = self
+            .local
+            .entry(pid)
+            .or_insert_with(|| (Process::monitor(pid), Vec::new()));
+
+        if groups.contains(&group) {
+            return;
+        }
+
+        groups.push(group.clone());
+
+        add_member(&self.scope, &group, pid);
+
+        let current = Process::current();
+
+        for peer in self.peers.keys() {
+            ProcessGroups::cast(
+                *peer,
+                ProcessGroupsMessage::PeerJoin(current, group.clone(), pid),
+            );
+        }
+    }
+
+    /// Removes a local process from a group, and notifies every peer.
+    fn leave_local(&mut self, group: String, pid: Pid) -> Result<(), ProcessGroupsError> {
+        let Some((monitor, groups)) = self.local.get_mut(&pid) else {
+            return Err(ProcessGroupsError::NotJoined);
+        };
+
+        let Some(index) = groups.iter().position(|joined| *joined == group) else {
+            return Err(ProcessGroupsError::NotJoined);
+        };
+
+        groups.remove(index);
+
+        if groups.is_empty() {
+            Process::demonitor(*monitor);
+
+            self.local.remove(&pid);
+        }
+
+        remove_member(&self.scope, &group, pid);
+
+        let current = Process::current();
+
+        for peer in self.peers.keys() {
+            ProcessGroups::cast(
+                *peer,
+                ProcessGroupsMessage::PeerLeave(current, group.clone(), pid),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Removes a local process that exited from every group it joined.
+    fn local_down(&mut self, pid: Pid) {
+        let Some((_, groups)) = self.local.remove(&pid) else {
+            return;
+        };
+
+        let current = Process::current();
+
+        for group in groups {
+            remove_member(&self.scope, &group, pid);
+
+            for peer in self.peers.keys() {
+                ProcessGroups::cast(
+                    *peer,
+                    ProcessGroupsMessage::PeerLeave(current, group.clone(), pid),
+                );
+            }
+        }
+    }
+
+    /// Sends our scope process to the scope on the given node.
+    fn discover(&self, node: Node) {
+        ProcessGroups::cast(
+            (self.scope.clone(), node),
+            ProcessGroupsMessage::Discover(Process::current()),
+        );
+    }
+
+    /// Starts tracking a peer scope process if it's not known yet, returns `true` if it's new.
+    fn peer_insert(&mut self, peer: Pid) -> bool {
+        if self.peers.contains_key(&peer) {
+            return false;
+        }
+
+        self.peers
+            .insert(peer, (Process::monitor(peer), BTreeMap::new()));
+
+        true
+    }
+
+    /// Replaces every membership of a peer with the given groups.
+    fn peer_sync(&mut self, peer: Pid, groups: Vec<(String, Vec<Pid>)>) {
+        self.peer_insert(peer);
+
+        let Some((_, memberships)) = self.peers.get_mut(&peer) else {
+            return;
+        };
+
+        for (group, members) in std::mem::take(memberships) {
+            for member in members {
+                remove_member(&self.scope, &group, member);
+            }
+        }
+
+        for (group, members) in groups {
+            for member in &members {
+                add_member(&self.scope, &group, *member);
+            }
+
+            memberships.insert(group, members);
+        }
+    }
+
+    /// Removes every membership of a peer scope that went down.
+    fn peer_down(&mut self, peer: Pid) {
+        let Some((_, memberships)) = self.peers.remove(&peer) else {
+            return;
+        };
+
+        for (group, members) in memberships {
+            for member in members {
+                remove_member(&self.scope, &group, member);
+            }
+        }
+    }
+
+    /// Returns the groups of every local member.
+    fn local_groups(&self) -> Vec<(String, Vec<Pid>)> {
+        let mut groups: BTreeMap<String, Vec<Pid>> = BTreeMap::new();
+
+        for (pid, (_, joined)) in &self.local {
+            for group in joined {
+                groups.entry(group.clone()).or_default().push(*pid);
+            }
+        }
+
+        groups.into_iter().collect()
+    }
+}
+
+impl Drop for ProcessGroups {
+    fn drop(&mut self) {
+        // A scope that failed to start must not remove the view of the running scope.
+        if self.running {
+            PROCESS_GROUPS.remove(&self.scope);
+        }
+    }
+}
+
+impl GenServer for ProcessGroups {
+    type Message = ProcessGroupsMessage;
+
+    async fn init(&mut self) -> Result<(), ExitReason> {
+        PROCESS_GROUPS.insert(self.scope.clone(), DashMap::new());
+
+        self.running = true;
+
+        Node::monitor_all();
+
+        for node in Node::list() {
+            self.discover(node);
+        }
+
+        Ok(())
+    }
+
+    async fn handle_cast(&mut self, message: Self::Message) -> Result<(), ExitReason> {
+        use ProcessGroupsMessage::*;
+
+        match message {
+            Discover(peer) => {
+                if self.peer_insert(peer) {
+                    ProcessGroups::cast(peer, Discover(Process::current()));
+                }
+
+                ProcessGroups::cast(peer, Sync(Process::current(), self.local_groups()));
+            }
+            Sync(peer, groups) => {
+                self.peer_sync(peer, groups);
+            }
+            PeerJoin(peer, group, pid) => {
+                if let Some((_, memberships)) = self.peers.get_mut(&peer) {
+                    memberships.entry(group.clone()).or_default().push(pid);
+
+                    add_member(&self.scope, &group, pid);
+                }
+            }
+            PeerLeave(peer, group, pid) => {
+                if let Some((_, memberships)) = self.peers.get_mut(&peer) {
+                    if let Some(members) = memberships.get_mut(&group) {
+                        members.retain(|member| *member != pid);
+
+                        if members.is_empty() {
+                            memberships.remove(&group);
+                        }
+                    }
+
+                    remove_member(&self.scope, &group, pid);
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    async fn handle_call(
+        &mut self,
+        message: Self::Message,
+        _from: From,
+    ) -> Result<Option<Self::Message>, ExitReason> {
+        use ProcessGroupsMessage::*;
+
+        match message {
+            Join(group, pid) => {
+                self.join_local(group, pid);
+
+                Ok(Some(JoinSuccess))
+            }
+            Leave(group, pid) => match self.leave_local(group, pid) {
+                Ok(()) => Ok(Some(LeaveSuccess)),
+                Err(error) => Ok(Some(LeaveError(error))),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    async fn handle_info(&mut self, info: Message<Self::Message>) -> Result<(), ExitReason> {
+        match info {
+            Message::System(SystemMessage::ProcessDown(Dest::Pid(pid), _, _)) => {
+                if pid.is_local() {
+                    self.local_down(pid);
+                } else {
+                    self.peer_down(pid);
+                }
+
+                Ok(())
+            }
+            Message::System(SystemMessage::NodeUp(node, _)) => {
+                self.discover(node);
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::convert::From<CallError> for ProcessGroupsError {
+    fn from(value: CallError) -> Self {
+        Self::CallError(value)
+    }
+}
+
+/// Adds a member to a group in the local view of the given scope.
+fn add_member(scope: &str, group: &str, pid: Pid) {
+    if let Some(scope) = PROCESS_GROUPS.get(scope) {
+        scope.entry(group.to_owned()).or_default().push(pid);
+    }
+}
+
+/// Removes a member from a group in the local view of the given scope.
+fn remove_member(scope: &str, group: &str, pid: Pid) {
+    if let Some(scope) = PROCESS_GROUPS.get(scope) {
+        scope.alter(group, |_, mut members| {
+            if let Some(index) = members.iter().position(|member| *member == pid) {
+                members.remove(index);
+            }
+
+            members
+        });
+
+        scope.remove_if(group, |_, members| members.is_empty());
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::SystemTime;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -8,6 +12,10 @@ use crate::Pid;
 pub struct ProcessInfo {
     /// The name, if any, that the process was registered under.
     pub registered_name: Option<String>,
+    /// The label, if any, that describes the process.
+    pub label: Option<String>,
+    /// The key/value metadata that describes the process.
+    pub metadata: BTreeMap<String, String>,
     /// The number of messages in this processes message queue.
     pub message_queue_len: usize,
     /// Whether or not the process is trapping exits.
@@ -16,6 +24,20 @@ pub struct ProcessInfo {
     pub links: Vec<Pid>,
     /// Collection of processes monitoring this process.
     pub monitored_by: Vec<Pid>,
+    /// The number of times the process was polled.
+    pub polls: u64,
+    /// The total time spent polling the process.
+    pub busy_time: Duration,
+    /// The last time the process was polled.
+    pub last_active: Option<SystemTime>,
+    /// The number of messages the process received.
+    pub messages_received: u64,
+    /// The number of messages the process sent.
+    pub messages_sent: u64,
+    /// The fixed size of the process future, in bytes, taken when it was spawned.
+    ///
+    /// This is a lower bound that never changes, it does not track heap allocations made by the process.
+    pub estimated_size: usize,
 }
 
 impl ProcessInfo {
@@ -23,10 +45,18 @@ impl ProcessInfo {
     pub const fn new() -> Self {
         Self {
             registered_name: None,
+            label: None,
+            metadata: BTreeMap::new(),
             message_queue_len: 0,
             trap_exit: false,
             links: Vec::new(),
             monitored_by: Vec::new(),
+            polls: 0,
+            busy_time: Duration::ZERO,
+            last_active: None,
+            messages_received: 0,
+            messages_sent: 0,
+            estimated_size: 0,
         }
     }
 }
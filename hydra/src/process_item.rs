@@ -1,8 +1,10 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use crate::Dest;
 use crate::ExitReason;
 use crate::Message;
+use crate::MessageFormat;
 use crate::Node;
 use crate::Pid;
 use crate::Reference;
@@ -11,7 +13,7 @@ use crate::SystemMessage;
 /// Represents a single unit of computation or a message for the process.
 pub enum ProcessItem {
     /// Sent from a remote process in a different userspace.
-    UserRemoteMessage(Vec<u8>),
+    UserRemoteMessage(MessageFormat, Arc<[u8]>),
     /// Sent from a process in the same userspace.
     UserLocalMessage(Box<dyn std::any::Any + Send>),
     /// Sent from the system.
@@ -19,7 +21,9 @@ pub enum ProcessItem {
     /// Sent from a monitor when a process goes down.
     MonitorProcessDown(Dest, Reference, ExitReason),
     /// Sent from a monitor when a node goes down.
-    MonitorNodeDown(Node, Reference),
+    MonitorNodeDown(Node, Reference, ExitReason),
+    /// Sent from a monitor on every node when a node comes up.
+    MonitorNodeUp(Node, Reference),
     /// Sent from a remote monitor to update the assigned pid.
     MonitorProcessUpdate(Reference, Pid),
     /// Sent from the system when an alias is deactivated externally.
@@ -47,7 +51,7 @@ where
 impl Debug for ProcessItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::UserRemoteMessage(_) => write!(f, "UserRemoteMessage(..)"),
+            Self::UserRemoteMessage(_, _) => write!(f, "UserRemoteMessage(..)"),
             Self::UserLocalMessage(_) => write!(f, "UserLocalMessage(..)"),
             Self::SystemMessage(system) => write!(f, "SystemMessage({:?})", system),
             Self::MonitorProcessDown(dest, reference, exit_reason) => write!(
@@ -55,8 +59,13 @@ impl Debug for ProcessItem {
                 "MonitorProcessDown({:?}, {:?}, {:?})",
                 dest, reference, exit_reason
             ),
-            Self::MonitorNodeDown(node, reference) => {
-                write!(f, "MonitorNodeDown({:?}, {:?})", node, reference)
+            Self::MonitorNodeDown(node, reference, exit_reason) => write!(
+                f,
+                "MonitorNodeDown({:?}, {:?}, {:?})",
+                node, reference, exit_reason
+            ),
+            Self::MonitorNodeUp(node, reference) => {
+                write!(f, "MonitorNodeUp({:?}, {:?})", node, reference)
             }
             Self::MonitorProcessUpdate(reference, process) => {
                 write!(f, "MonitorProcessUpdate({:?}, {:?})", reference, process)
@@ -1,10 +1,12 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::frame::Frame;
 use crate::frame::Send;
 use crate::frame::SendTarget;
 
 use crate::alias_retrieve;
+use crate::global_name_lookup;
 use crate::node_process_send_with_alias;
 use crate::node_process_send_with_name;
 use crate::node_process_send_with_pid;
@@ -16,6 +18,7 @@ use crate::serialize_value;
 use crate::Dest;
 use crate::Dests;
 use crate::Message;
+use crate::MessageFormat;
 use crate::ProcessItem;
 use crate::Receivable;
 
@@ -27,45 +30,28 @@ pub fn process_send<M: Receivable>(dests: Dests, message: M) {
         }
         Dests::Dests(dests) => {
             let mut first_local_process: Option<Dest> = None;
-            let mut serialized: Option<Vec<u8>> = None;
 
-            // Delay serialization until it's absolutely necessary to have it.
-            let mut get_serialized = || {
-                if let Some(serialized) = &serialized {
-                    serialized.clone()
-                } else {
-                    let message = serialize_value(&message);
-
-                    serialized = Some(message.clone());
-
-                    message
-                }
-            };
+            // Delay serialization until it's absolutely necessary to have it, then share it with every destination.
+            let mut serialized: Option<(MessageFormat, Arc<[u8]>)> = None;
 
             let mut remote_sends: BTreeMap<u64, Send> = BTreeMap::new();
 
             for dest in dests {
+                let Some(dest) = dest.resolve() else {
+                    continue;
+                };
+
                 if dest.is_local() && first_local_process.is_none() {
                     first_local_process = Some(dest);
                     continue;
                 }
 
+                let (format, message) = serialized.get_or_insert_with(|| serialize_value(&message));
+
                 if dest.is_local() {
-                    process_send_unoptimal(dest, get_serialized());
+                    process_send_unoptimal(dest, *format, message.clone());
                 } else {
-                    let (node, send_target) = match dest {
-                        Dest::Pid(pid) => (pid.node(), SendTarget::from(pid)),
-                        Dest::Named(name, node) => {
-                            (node_register(node, false), SendTarget::from(name))
-                        }
-                        Dest::Alias(reference) => (reference.node(), SendTarget::from(reference)),
-                    };
-
-                    remote_sends
-                        .entry(node)
-                        .or_insert(Send::with_message(get_serialized()))
-                        .targets
-                        .push(send_target);
+                    process_send_remote(&mut remote_sends, dest, *format, message);
                 }
             }
 
@@ -82,23 +68,82 @@ pub fn process_send<M: Receivable>(dests: Dests, message: M) {
     }
 }
 
+/// Sends a message to one or more destinations, cloning it for local destinations, and serializing it once for remote ones.
+pub fn process_broadcast<M: Receivable + Clone>(dests: Dests, message: M) {
+    let dests = match dests {
+        Dests::Dest(dest) => return process_send_optimal(dest, message),
+        Dests::Dests(dests) => dests,
+    };
+
+    let mut serialized: Option<(MessageFormat, Arc<[u8]>)> = None;
+
+    let mut remote_sends: BTreeMap<u64, Send> = BTreeMap::new();
+
+    for dest in dests {
+        let Some(dest) = dest.resolve() else {
+            continue;
+        };
+
+        if dest.is_local() {
+            process_send_optimal(dest, message.clone());
+        } else {
+            let (format, message) = serialized.get_or_insert_with(|| serialize_value(&message));
+
+            process_send_remote(&mut remote_sends, dest, *format, message);
+        }
+    }
+
+    // Optimally send one packet per target node, sharing the serialized message.
+    for (node, send) in remote_sends {
+        node_send_frame(Frame::from(send), node);
+    }
+}
+
+/// Adds a remote destination to the send frame for its node.
+fn process_send_remote(
+    remote_sends: &mut BTreeMap<u64, Send>,
+    dest: Dest,
+    format: MessageFormat,
+    message: &Arc<[u8]>,
+) {
+    let (node, send_target) = match dest {
+        Dest::Pid(pid) => (pid.node(), SendTarget::from(pid)),
+        Dest::Named(name, node) => (node_register(node, false), SendTarget::from(name)),
+        Dest::Alias(reference) => (reference.node(), SendTarget::from(reference)),
+        // Global names are resolved before sending.
+        Dest::Global(_) => unreachable!(),
+    };
+
+    remote_sends
+        .entry(node)
+        .or_insert_with(|| Send::with_message(format, message.clone()))
+        .targets
+        .push(send_target);
+}
+
 /// Sends a single message to the target destination, avoiding T: Clone.
 ///
 /// TODO: This is a suboptimal path, that can be replaced once specialization lands by specializing on T: Clone.
-fn process_send_unoptimal(dest: Dest, message: Vec<u8>) {
+fn process_send_unoptimal(dest: Dest, format: MessageFormat, message: Arc<[u8]>) {
     match dest {
         Dest::Pid(pid) => {
-            process_sender(pid).map(|sender| sender.send(ProcessItem::UserRemoteMessage(message)));
+            process_sender(pid)
+                .map(|sender| sender.send(ProcessItem::UserRemoteMessage(format, message)));
         }
         Dest::Named(name, _) => {
             process_name_lookup(name.as_ref())
                 .and_then(process_sender)
-                .map(|sender| sender.send(ProcessItem::UserRemoteMessage(message)));
+                .map(|sender| sender.send(ProcessItem::UserRemoteMessage(format, message)));
         }
         Dest::Alias(reference) => {
-            alias_retrieve(reference)
-                .map(|alias| alias.sender.send(ProcessItem::UserRemoteMessage(message)));
+            alias_retrieve(reference).map(|alias| {
+                alias
+                    .sender
+                    .send(ProcessItem::UserRemoteMessage(format, message))
+            });
         }
+        // Global names are resolved before sending.
+        Dest::Global(_) => unreachable!(),
     }
 }
 
@@ -129,5 +174,10 @@ fn process_send_optimal<M: Receivable>(dest: Dest, message: M) {
                 node_process_send_with_alias(reference, message);
             }
         }
+        Dest::Global(name) => {
+            if let Some(pid) = global_name_lookup(&name) {
+                process_send_optimal(Dest::Pid(pid), message);
+            }
+        }
     }
 }
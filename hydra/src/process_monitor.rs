@@ -2,9 +2,12 @@ use crate::Node;
 use crate::Pid;
 
 /// Represents an installed monitor's data for a process.
+#[allow(clippy::enum_variant_names)]
 pub enum ProcessMonitor {
     /// A monitor installed to monitor the given process.
     ForProcess(Option<Pid>),
     /// A monitor installed to monitor the given node.
     ForNode(Node),
+    /// A monitor installed to monitor every node.
+    ForAllNodes,
 }
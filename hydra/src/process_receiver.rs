@@ -155,16 +155,19 @@ where
 /// Converts a processed item into a message.
 #[inline(always)]
 fn convert_item<T: Receivable>(item: ProcessItem) -> Message<T> {
+    PROCESS.with(|process| process.stats.record_received());
+
     match item {
         // If we got here, the deserialization has already taken place.
-        ProcessItem::UserRemoteMessage(_) => unreachable!(),
+        ProcessItem::UserRemoteMessage(_, _) => unreachable!(),
         ProcessItem::UserLocalMessage(deserialized) => {
             deserialized.downcast().map(|x| Message::User(*x)).unwrap()
         }
         ProcessItem::SystemMessage(system) => Message::System(system),
         // Handled during item processing.
         ProcessItem::MonitorProcessDown(_, _, _) => unreachable!(),
-        ProcessItem::MonitorNodeDown(_, _) => unreachable!(),
+        ProcessItem::MonitorNodeDown(_, _, _) => unreachable!(),
+        ProcessItem::MonitorNodeUp(_, _) => unreachable!(),
         ProcessItem::MonitorProcessUpdate(_, _) => unreachable!(),
         ProcessItem::AliasDeactivated(_) => unreachable!(),
     }
@@ -175,8 +178,8 @@ fn convert_item<T: Receivable>(item: ProcessItem) -> Message<T> {
 fn process_item<T: Receivable>(item: &mut ProcessItem) -> Result<Option<Message<&T>>, ()> {
     // Special case for serialized messages, we'll convert them to deserialized one time to prevent
     // deserializing the value more than once, then convert to a reference.
-    if let ProcessItem::UserRemoteMessage(serialized) = item {
-        let result: Result<T, _> = deserialize_value(serialized);
+    if let ProcessItem::UserRemoteMessage(format, serialized) = item {
+        let result: Result<T, _> = deserialize_value(*format, serialized);
 
         if let Ok(result) = result {
             *item = ProcessItem::UserLocalMessage(Box::new(result));
@@ -187,7 +190,7 @@ fn process_item<T: Receivable>(item: &mut ProcessItem) -> Result<Option<Message<
 
     match item {
         // Explicitly handled first, so that we can process the other values later.
-        ProcessItem::UserRemoteMessage(_) => unreachable!(),
+        ProcessItem::UserRemoteMessage(_, _) => unreachable!(),
         ProcessItem::UserLocalMessage(deserialized) => deserialized
             .downcast_ref()
             .map(Message::User)
@@ -208,14 +211,38 @@ fn process_item<T: Receivable>(item: &mut ProcessItem) -> Result<Option<Message<
 
             Ok(Some(Message::System(system)))
         }
-        ProcessItem::MonitorNodeDown(node, reference) => {
-            if PROCESS.with(|process| process.monitors.borrow_mut().remove(reference).is_none()) {
+        ProcessItem::MonitorNodeDown(node, reference, exit_reason) => {
+            let active = PROCESS.with(|process| {
+                let mut monitors = process.monitors.borrow_mut();
+
+                // Monitors for every node stay installed until the process calls demonitor.
+                match monitors.get(reference) {
+                    Some(ProcessMonitor::ForAllNodes) => true,
+                    Some(_) => monitors.remove(reference).is_some(),
+                    None => false,
+                }
+            });
+
+            if !active {
                 // If the process has already called demonitor, discard the message.
                 // This prevents the need for a flush option.
                 return Ok(None);
             }
 
-            let system = SystemMessage::NodeDown(node.clone(), *reference);
+            let system = SystemMessage::NodeDown(node.clone(), *reference, exit_reason.clone());
+
+            // Make sure processing only happens one time.
+            *item = ProcessItem::SystemMessage(system.clone());
+
+            Ok(Some(Message::System(system)))
+        }
+        ProcessItem::MonitorNodeUp(node, reference) => {
+            if PROCESS.with(|process| !process.monitors.borrow().contains_key(reference)) {
+                // If the process has already called demonitor, discard the message.
+                return Ok(None);
+            }
+
+            let system = SystemMessage::NodeUp(node.clone(), *reference);
 
             // Make sure processing only happens one time.
             *item = ProcessItem::SystemMessage(system.clone());
@@ -1,8 +1,12 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use tokio::task::JoinHandle;
 
 use crate::ExitReason;
 use crate::ProcessFlags;
 use crate::ProcessSend;
+use crate::ProcessStats;
 
 /// Process registration information.
 pub struct ProcessRegistration {
@@ -12,21 +16,34 @@ pub struct ProcessRegistration {
     pub sender: ProcessSend,
     /// Registered name of this process or [None] when unregistered.
     pub name: Option<String>,
+    /// A label describing this process.
+    pub label: Option<String>,
+    /// Key/value metadata describing this process.
+    pub metadata: BTreeMap<String, String>,
     /// Process flags.
     pub flags: ProcessFlags,
     /// Process exit reason.
     pub exit_reason: Option<ExitReason>,
+    /// Process runtime statistics.
+    pub stats: Arc<ProcessStats>,
 }
 
 impl ProcessRegistration {
-    /// Constructs a new [ProcessRegistration] from a given task handle, and channel.
-    pub const fn new(handle: JoinHandle<()>, sender: ProcessSend) -> Self {
+    /// Constructs a new [ProcessRegistration] from a given task handle, channel, and stats.
+    pub const fn new(
+        handle: JoinHandle<()>,
+        sender: ProcessSend,
+        stats: Arc<ProcessStats>,
+    ) -> Self {
         Self {
             handle,
             sender,
             name: None,
+            label: None,
+            metadata: BTreeMap::new(),
             flags: ProcessFlags::empty(),
             exit_reason: None,
+            stats,
         }
     }
 }
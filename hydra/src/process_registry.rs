@@ -58,9 +58,25 @@ pub fn process_name_lookup(name: &str) -> Option<Pid> {
         .map(|process_id| Pid::local(*process_id))
 }
 
-/// Inserts a new process registration.
+/// Inserts a new process registration, panics if the id is already registered.
 pub fn process_insert(id: u64, registration: ProcessRegistration) {
-    PROCESS_REGISTRY.insert(id, registration);
+    match PROCESS_REGISTRY.entry(id) {
+        Entry::Occupied(_) => panic!("Process id {} is already registered!", id),
+        Entry::Vacant(entry) => {
+            entry.insert(registration);
+        }
+    }
+}
+
+/// Reserves the given name for a process that is about to be inserted, returns `false` if the name is taken.
+pub fn process_name_reserve(name: String, id: u64) -> bool {
+    match PROCESS_NAMES.entry(name) {
+        Entry::Occupied(_) => false,
+        Entry::Vacant(entry) => {
+            entry.insert(id);
+            true
+        }
+    }
 }
 
 /// Removes a registered name.
@@ -168,7 +184,7 @@ pub fn process_exit(pid: Pid, from: Pid, exit_reason: ExitReason) {
                 process.exit_reason = Some(exit_reason);
                 process.handle.abort();
             }
-            ExitReason::Custom(_) => {
+            ExitReason::Custom(_) | ExitReason::Payload(_, _) | ExitReason::Panic(_) => {
                 if pid == from || !trapping_exits {
                     process.exit_reason = Some(exit_reason);
                     process.handle.abort();
@@ -221,6 +237,41 @@ pub fn process_set_flags(pid: Pid, flags: ProcessFlags) {
     });
 }
 
+/// Sets the process label.
+pub fn process_set_label(pid: Pid, label: Option<String>) {
+    PROCESS_REGISTRY.alter(&pid.id(), |_, mut process| {
+        process.label = label;
+        process
+    });
+}
+
+/// Sets, or removes when [None], a process metadata value.
+pub fn process_set_metadata(pid: Pid, key: String, value: Option<String>) {
+    PROCESS_REGISTRY.alter(&pid.id(), |_, mut process| {
+        match value {
+            Some(value) => process.metadata.insert(key, value),
+            None => process.metadata.remove(&key),
+        };
+
+        process
+    });
+}
+
+/// Describes a supervised process with it's child id, which is also used as the label if the process has none.
+pub fn process_set_child_id(pid: Pid, id: &str) {
+    PROCESS_REGISTRY.alter(&pid.id(), |_, mut process| {
+        if process.label.is_none() {
+            process.label = Some(id.to_string());
+        }
+
+        process
+            .metadata
+            .insert(String::from("child_id"), id.to_string());
+
+        process
+    });
+}
+
 /// Sets the process exit reason.
 pub fn process_set_exit_reason(pid: Pid, exit_reason: ExitReason) {
     PROCESS_REGISTRY.alter(&pid.id(), |_, mut process| {
@@ -278,6 +329,14 @@ pub fn process_info(pid: Pid) -> Option<ProcessInfo> {
     let mut info = ProcessInfo::new();
 
     info.registered_name.clone_from(&process.name);
+    info.label.clone_from(&process.label);
+    info.metadata.clone_from(&process.metadata);
+    info.polls = process.stats.polls();
+    info.busy_time = process.stats.busy_time();
+    info.last_active = process.stats.last_active();
+    info.messages_received = process.stats.messages_received();
+    info.messages_sent = process.stats.messages_sent();
+    info.estimated_size = process.stats.estimated_size();
     info.message_queue_len = process.sender.len();
     info.trap_exit = process.flags.contains(ProcessFlags::TRAP_EXIT);
 
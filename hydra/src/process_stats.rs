@@ -0,0 +1,150 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+
+use pin_project_lite::pin_project;
+
+/// The instant all process activity timestamps are relative to.
+static PROCESS_STATS_EPOCH: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Represents a process that has never been polled.
+const NEVER: u64 = u64::MAX;
+
+/// Runtime statistics for a local process.
+pub struct ProcessStats {
+    /// The number of times the process was polled.
+    polls: AtomicU64,
+    /// The total time spent polling the process in nanoseconds.
+    busy_time: AtomicU64,
+    /// The last time the process was polled in microseconds since the epoch.
+    last_active: AtomicU64,
+    /// The number of messages received.
+    messages_received: AtomicU64,
+    /// The number of messages sent.
+    messages_sent: AtomicU64,
+    /// The size of the process future.
+    estimated_size: usize,
+}
+
+impl ProcessStats {
+    /// Constructs a new instance of [ProcessStats] for a process future of the given size.
+    pub fn new(estimated_size: usize) -> Self {
+        Self {
+            polls: AtomicU64::new(0),
+            busy_time: AtomicU64::new(0),
+            last_active: AtomicU64::new(NEVER),
+            messages_received: AtomicU64::new(0),
+            messages_sent: AtomicU64::new(0),
+            estimated_size,
+        }
+    }
+
+    /// Records a poll that started and ended at the given instants.
+    pub fn record_poll(&self, start: Instant, end: Instant) {
+        let busy_time = end.duration_since(start).as_nanos() as u64;
+        let last_active = end.duration_since(*PROCESS_STATS_EPOCH).as_micros() as u64;
+
+        self.polls.fetch_add(1, Ordering::Relaxed);
+        self.busy_time.fetch_add(busy_time, Ordering::Relaxed);
+        self.last_active.store(last_active, Ordering::Relaxed);
+    }
+
+    /// Records a message that was received.
+    pub fn record_received(&self) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the given number of messages that were sent.
+    pub fn record_sent(&self, count: usize) {
+        self.messages_sent
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the number of times the process was polled.
+    pub fn polls(&self) -> u64 {
+        self.polls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total time spent polling the process.
+    pub fn busy_time(&self) -> Duration {
+        Duration::from_nanos(self.busy_time.load(Ordering::Relaxed))
+    }
+
+    /// Returns the last time the process was polled.
+    pub fn last_active(&self) -> Option<SystemTime> {
+        match self.last_active.load(Ordering::Relaxed) {
+            NEVER => None,
+            micros => {
+                let since = PROCESS_STATS_EPOCH
+                    .elapsed()
+                    .saturating_sub(Duration::from_micros(micros));
+
+                SystemTime::now().checked_sub(since)
+            }
+        }
+    }
+
+    /// Returns the number of messages received.
+    pub fn messages_received(&self) -> u64 {
+        self.messages_received.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages sent.
+    pub fn messages_sent(&self) -> u64 {
+        self.messages_sent.load(Ordering::Relaxed)
+    }
+
+    /// Returns the size of the process future.
+    pub fn estimated_size(&self) -> usize {
+        self.estimated_size
+    }
+}
+
+pin_project! {
+    /// A future that records the time spent polling a process.
+    pub struct ProcessStatsFuture<Fut>
+    where
+        Fut: Future,
+    {
+        #[pin]
+        future: Fut,
+        stats: Arc<ProcessStats>,
+    }
+}
+
+impl<Fut> ProcessStatsFuture<Fut>
+where
+    Fut: Future,
+{
+    /// Constructs a new [ProcessStatsFuture] that records to the given stats.
+    pub fn new(future: Fut, stats: Arc<ProcessStats>) -> Self {
+        Self { future, stats }
+    }
+}
+
+impl<Fut> Future for ProcessStatsFuture<Fut>
+where
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let start = Instant::now();
+        let result = this.future.poll(cx);
+
+        this.stats.record_poll(start, Instant::now());
+
+        result
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+/// Controls how the local node reconnects to a remote node that went down unexpectedly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReconnectPolicy {
+    /// The node is never reconnected, it falls back to a known node.
+    #[default]
+    Never,
+    /// The node is reconnected after the given interval, until a connection succeeds.
+    Interval(Duration),
+    /// The node is reconnected after an exponentially increasing delay starting at `initial`,
+    /// which is capped at `max`, until a connection succeeds.
+    Backoff { initial: Duration, max: Duration },
+}
+
+impl ReconnectPolicy {
+    /// Returns the delay before the given reconnect `attempt` (starting at 1), or [None] if we should not reconnect.
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        match self {
+            Self::Never => None,
+            Self::Interval(interval) => Some(*interval),
+            Self::Backoff { initial, max } => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+                Some(initial.saturating_mul(factor).min(*max))
+            }
+        }
+    }
+}
@@ -1,7 +1,13 @@
+use std::any::Any;
 use std::collections::BTreeMap;
 use std::future::Future;
+use std::net::SocketAddr;
 use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use dashmap::DashMap;
 
@@ -10,6 +16,9 @@ use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::node_lookup_local;
+use crate::node_lookup_remote;
+use crate::shutdown_brutal_kill;
 use crate::shutdown_infinity;
 use crate::shutdown_timeout;
 use crate::CallError;
@@ -24,13 +33,38 @@ use crate::Node;
 use crate::Pid;
 use crate::Process;
 use crate::ProcessFlags;
+use crate::Receivable;
 use crate::Reference;
+use crate::RegistryEvent;
 use crate::RegistryOptions;
+use crate::RegistryRing;
 use crate::Shutdown;
 use crate::SystemMessage;
 
 /// A local collection of active process registries.
-static REGISTRY: Lazy<DashMap<String, DashMap<RegistryKey, Pid>>> = Lazy::new(DashMap::new);
+static REGISTRY: Lazy<DashMap<String, DashMap<RegistryKey, RegistryEntry>>> =
+    Lazy::new(DashMap::new);
+
+/// The instant that registry access times are measured from.
+static REGISTRY_EPOCH: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// A local collection of the entries of every registry with duplicate keys.
+static REGISTRY_ENTRIES: Lazy<DashMap<String, RegistryEntries>> = Lazy::new(DashMap::new);
+
+/// The rings of nodes that own the keys of each distributed registry.
+static REGISTRY_RINGS: Lazy<DashMap<String, RegistryRing>> = Lazy::new(DashMap::new);
+
+/// The metadata attached to an entry in a registry with duplicate keys.
+type RegistryValue = Arc<dyn Any + Send + Sync>;
+
+/// The entries of a registry with duplicate keys.
+type RegistryEntries = DashMap<RegistryKey, Vec<(Pid, RegistryValue)>>;
+
+/// A registered process, along with the last time it was looked up.
+struct RegistryEntry {
+    pid: Pid,
+    accessed: AtomicU64,
+}
 
 /// A registry key.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -49,6 +83,13 @@ pub enum RegistryKey {
     UInteger128(u128),
     /// A string key.
     String(String),
+    /// A raw byte key.
+    Bytes(Vec<u8>),
+    /// A composite key made of other keys.
+    Tuple(Vec<RegistryKey>),
+    /// A uuid key.
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
 }
 
 /// A registry message.
@@ -73,6 +114,10 @@ pub enum RegistryMessage {
     Remove(RegistryKey),
     RemoveSuccess(Option<Pid>),
     RemoveLookup(Pid),
+    Discover(Pid),
+    MonitorEntries(Pid),
+    DemonitorEntries(Pid),
+    EvictIdle,
 }
 
 /// Errors for [Registry] calls.
@@ -88,6 +133,8 @@ pub enum RegistryError {
     AlreadyStarted(Pid),
     /// The process was not found for the given key.
     NotFound,
+    /// The registry wasn't configured with duplicate keys.
+    RegisterNotSupported,
 }
 
 /// Provides a centralized 'registry' of processes using any value as a key.
@@ -105,7 +152,15 @@ pub struct Registry {
         >,
     >,
     shutdown: Shutdown,
+    max_entries: Option<usize>,
+    idle_timeout: Option<Duration>,
+    event_handler: Option<Arc<dyn Fn(RegistryEvent) + Send + Sync>>,
     lookup: BTreeMap<Pid, RegistryKey>,
+    distributed: bool,
+    peers: BTreeMap<Pid, (Reference, Node)>,
+    duplicate_keys: bool,
+    entries: BTreeMap<Pid, Reference>,
+    running: bool,
 }
 
 impl Registry {
@@ -118,7 +173,15 @@ impl Registry {
             name: name.into(),
             start: None,
             shutdown: Shutdown::BrutalKill,
+            max_entries: None,
+            idle_timeout: None,
+            event_handler: None,
             lookup: BTreeMap::new(),
+            distributed: false,
+            peers: BTreeMap::new(),
+            duplicate_keys: false,
+            entries: BTreeMap::new(),
+            running: false,
         }
     }
 
@@ -135,12 +198,59 @@ impl Registry {
         self
     }
 
-    /// Sets the method used to shutdown any registered processes once when the [Registry] is terminated.
+    /// Spreads the keys of this registry across every connected node running a registry with the same name.
+    ///
+    /// Each key is owned by a node chosen with a [HashRing], so `lookup_or_start` starts the process on the owning node.
+    /// When a node joins, processes for keys it now owns are exited with the `handoff` reason, and started on the new owner when next requested.
+    /// When a node goes down, it's keys are owned by the remaining nodes right away.
+    pub fn with_distribution(mut self) -> Self {
+        self.distributed = true;
+        self
+    }
+
+    /// Allows many processes to register themselves under the same key, each with it's own metadata.
+    ///
+    /// Processes register with [Registry::register], and are removed automatically when they exit.
+    pub fn with_duplicate_keys(mut self) -> Self {
+        self.duplicate_keys = true;
+        self
+    }
+
+    /// Sets the method used to shutdown any registered processes once when the [Registry] is terminated, or when they are evicted.
     pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
         self.shutdown = shutdown;
         self
     }
 
+    /// Sets the maximum number of processes the registry will start, once full the least recently looked up process is evicted. (Default unlimited)
+    ///
+    /// Finding the least recently looked up process scans every entry, so each start past the limit costs O(n).
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Sets the duration a process can go without being looked up before it's evicted. (Default never)
+    ///
+    /// Idle processes are checked for every half of the timeout, which must be greater than zero.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        if idle_timeout.is_zero() {
+            panic!("Idle timeout must be greater than zero!");
+        }
+
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Sets a handler that is called from the registry process whenever a process is registered, removed, or evicted.
+    pub fn with_event_handler<T>(mut self, event_handler: T) -> Self
+    where
+        T: Fn(RegistryEvent) + Send + Sync + 'static,
+    {
+        self.event_handler = Some(Arc::new(event_handler));
+        self
+    }
+
     /// Looks up a running process.
     ///
     /// If the registry is local, this will just query the table without hitting the registry process.
@@ -150,8 +260,8 @@ impl Registry {
     ) -> Result<Option<Pid>, RegistryError> {
         use RegistryMessage::*;
 
-        let registry = registry.into();
         let key = key.into();
+        let registry = registry_route(registry.into(), &key);
 
         if let Dest::Named(registry, Node::Local) = &registry {
             return Ok(lookup_process(registry, &key));
@@ -172,8 +282,8 @@ impl Registry {
     ) -> Result<Pid, RegistryError> {
         use RegistryMessage::*;
 
-        let registry = registry.into();
         let key = key.into();
+        let registry = registry_route(registry.into(), &key);
 
         if let Dest::Named(registry, Node::Local) = &registry {
             if let Some(result) = lookup_process(registry, &key) {
@@ -197,7 +307,10 @@ impl Registry {
     ) -> Result<Pid, RegistryError> {
         use RegistryMessage::*;
 
-        match Registry::call(registry, Start(key.into()), None).await? {
+        let key = key.into();
+        let registry = registry_route(registry.into(), &key);
+
+        match Registry::call(registry, Start(key), None).await? {
             StartSuccess(pid) => Ok(pid),
             StartError(error) => Err(error),
             _ => unreachable!(),
@@ -215,7 +328,10 @@ impl Registry {
     ) -> Result<(), RegistryError> {
         use RegistryMessage::*;
 
-        match Registry::call(registry, Stop(key.into()), None).await? {
+        let key = key.into();
+        let registry = registry_route(registry.into(), &key);
+
+        match Registry::call(registry, Stop(key), None).await? {
             StopSuccess => Ok(()),
             StopError(error) => Err(error),
             _ => unreachable!(),
@@ -223,6 +339,8 @@ impl Registry {
     }
 
     /// Returns the number of registered processes for the given `registry`.
+    ///
+    /// For a distributed registry, only the processes owned by the node of the given `registry` are counted.
     pub async fn count<T: Into<Dest>>(registry: T) -> Result<usize, RegistryError> {
         use RegistryMessage::*;
 
@@ -240,6 +358,8 @@ impl Registry {
 
     /// Returns a list of every process registered to the given `registry`.
     ///
+    /// For a distributed registry, only the processes owned by the node of the given `registry` are listed.
+    ///
     /// There is no ordering guarantee.
     pub async fn list<T: Into<Dest>>(
         registry: T,
@@ -267,8 +387,8 @@ impl Registry {
     ) -> Result<Option<Pid>, RegistryError> {
         use RegistryMessage::*;
 
-        let registry = registry.into();
         let key = key.into();
+        let registry = registry_route(registry.into(), &key);
 
         if let Dest::Named(registry, Node::Local) = &registry {
             let Some(process) = remove_process(registry, &key) else {
@@ -286,6 +406,149 @@ impl Registry {
         }
     }
 
+    /// Returns the node that owns `key` in a local distributed `registry`.
+    ///
+    /// Returns `None` if the registry isn't distributed, or the local node isn't started.
+    pub fn owner<T: AsRef<str>, N: Into<RegistryKey>>(registry: T, key: N) -> Option<Node> {
+        registry_owner(registry, &key.into())
+    }
+
+    /// Registers the current process under `key` with the given metadata, in a local `registry` with duplicate keys.
+    ///
+    /// A process may register under the same key more than once.
+    pub fn register<T: AsRef<str>, N: Into<RegistryKey>, V: Any + Send + Sync>(
+        registry: T,
+        key: N,
+        value: V,
+    ) -> Result<(), RegistryError> {
+        let registry = registry.as_ref();
+        let current = Process::current();
+
+        let Some(entries) = REGISTRY_ENTRIES.get(registry) else {
+            return Err(RegistryError::RegisterNotSupported);
+        };
+
+        entries
+            .entry(key.into())
+            .or_default()
+            .push((current, Arc::new(value)));
+
+        Registry::cast(
+            registry.to_string(),
+            RegistryMessage::MonitorEntries(current),
+        );
+
+        Ok(())
+    }
+
+    /// Removes every entry of the current process under `key`, in a local `registry` with duplicate keys.
+    pub fn unregister<T: AsRef<str>, N: Into<RegistryKey>>(registry: T, key: N) {
+        let registry = registry.as_ref();
+        let current = Process::current();
+
+        let Some(entries) = REGISTRY_ENTRIES.get(registry) else {
+            return;
+        };
+
+        remove_entries(&entries, &key.into(), current);
+
+        if !has_entries(&entries, current) {
+            Registry::cast(
+                registry.to_string(),
+                RegistryMessage::DemonitorEntries(current),
+            );
+        }
+    }
+
+    /// Returns every process registered under `key` with metadata of type `V`, in a local `registry` with duplicate keys.
+    pub fn lookup_entries<T: AsRef<str>, N: Into<RegistryKey>, V: Any + Clone>(
+        registry: T,
+        key: N,
+    ) -> Vec<(Pid, V)> {
+        Registry::match_entries(registry, key, |_: &V| true)
+    }
+
+    /// Returns every process registered under `key` with metadata of type `V` that matches `filter`, in a local `registry` with duplicate keys.
+    pub fn match_entries<T, N, V, F>(registry: T, key: N, filter: F) -> Vec<(Pid, V)>
+    where
+        T: AsRef<str>,
+        N: Into<RegistryKey>,
+        V: Any + Clone,
+        F: Fn(&V) -> bool,
+    {
+        let Some(entries) = REGISTRY_ENTRIES.get(registry.as_ref()) else {
+            return Vec::new();
+        };
+
+        let Some(entries) = entries.get(&key.into()) else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|(pid, value)| {
+                value
+                    .downcast_ref::<V>()
+                    .filter(|value| filter(value))
+                    .map(|value| (*pid, value.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns every entry with metadata of type `V` that matches `filter`, over every key in a local `registry` with duplicate keys.
+    ///
+    /// There is no ordering guarantee.
+    pub fn select<T, V, F>(registry: T, filter: F) -> Vec<(RegistryKey, Pid, V)>
+    where
+        T: AsRef<str>,
+        V: Any + Clone,
+        F: Fn(&RegistryKey, Pid, &V) -> bool,
+    {
+        let Some(entries) = REGISTRY_ENTRIES.get(registry.as_ref()) else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+
+        for entry in entries.iter() {
+            for (pid, value) in entry.value() {
+                if let Some(value) = value.downcast_ref::<V>() {
+                    if filter(entry.key(), *pid, value) {
+                        result.push((entry.key().clone(), *pid, value.clone()));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Sends `message` to every process registered under `key`, in a local `registry` with duplicate keys.
+    ///
+    /// Returns the number of processes the message was sent to.
+    pub fn dispatch<T: AsRef<str>, N: Into<RegistryKey>, M: Receivable + Clone>(
+        registry: T,
+        key: N,
+        message: M,
+    ) -> usize {
+        let pids: Vec<Pid> = REGISTRY_ENTRIES
+            .get(registry.as_ref())
+            .and_then(|entries| {
+                entries
+                    .get(&key.into())
+                    .map(|entries| entries.iter().map(|(pid, _)| *pid).collect())
+            })
+            .unwrap_or_default();
+
+        let count = pids.len();
+
+        if count > 0 {
+            Process::broadcast(pids, message);
+        }
+
+        count
+    }
+
     /// Create a registry proces not linked to a supervision tree.
     pub async fn start(self, mut options: RegistryOptions) -> Result<Pid, ExitReason> {
         if options.name.is_none() {
@@ -332,6 +595,16 @@ impl Registry {
             return Err(RegistryError::AlreadyStarted(process));
         }
 
+        if let Some(max_entries) = self.max_entries {
+            while count_processes(&self.name) >= max_entries {
+                let Some(key) = least_recent_process(&self.name) else {
+                    break;
+                };
+
+                self.evict(key);
+            }
+        }
+
         let start_child = Pin::from(self.start.as_ref().unwrap()(key.clone())).await;
 
         match start_child {
@@ -341,7 +614,9 @@ impl Registry {
 
                 self.lookup.insert(pid, key.clone());
 
-                register_process(&self.name, key, pid);
+                register_process(&self.name, key.clone(), pid);
+
+                self.event(RegistryEvent::Registered(key, pid));
 
                 Ok(pid)
             }
@@ -401,6 +676,8 @@ impl Registry {
 
         remove_process(&self.name, &key);
 
+        self.event(RegistryEvent::Removed(key, process));
+
         Ok(())
     }
 
@@ -417,6 +694,8 @@ impl Registry {
 
         self.lookup.remove(&process);
 
+        self.event(RegistryEvent::Removed(key, process));
+
         Some(process)
     }
 
@@ -433,16 +712,199 @@ impl Registry {
         let _ = reason;
 
         REGISTRY.alter(&self.name, |_, value| {
-            value.remove_if(&key, |_, value| *value == pid);
+            value.remove_if(&key, |_, value| value.pid == pid);
             value
         });
+
+        self.event(RegistryEvent::Removed(key, pid));
+    }
+
+    /// Removes the process that was looked up, if it hasn't been removed yet.
+    fn remove_lookup(&mut self, pid: Pid) {
+        Process::unlink(pid);
+
+        if let Some(key) = self.lookup.remove(&pid) {
+            self.event(RegistryEvent::Removed(key, pid));
+        }
+    }
+
+    /// Evicts the process with the given key, stopping it with the configured shutdown.
+    fn evict(&mut self, key: RegistryKey) {
+        let Some(process) = remove_process(&self.name, &key) else {
+            return;
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(child_key = ?key, child_pid = ?process, "Evicted registered process");
+
+        Process::unlink(process);
+
+        self.lookup.remove(&process);
+
+        let shutdown = self.shutdown;
+
+        Process::spawn(async move {
+            let monitor = Process::monitor(process);
+
+            let _ = match shutdown {
+                Shutdown::BrutalKill => shutdown_brutal_kill(process, monitor).await,
+                Shutdown::Duration(timeout) => shutdown_timeout(process, monitor, timeout).await,
+                Shutdown::Infinity => shutdown_infinity(process, monitor).await,
+            };
+        });
+
+        self.event(RegistryEvent::Evicted(key, process));
+    }
+
+    /// Evicts every process that hasn't been looked up within the idle timeout.
+    fn evict_idle(&mut self, idle_timeout: Duration) {
+        let idle = registry_now().saturating_sub(idle_timeout.as_millis() as u64);
+
+        let keys: Vec<RegistryKey> = REGISTRY
+            .get(&self.name)
+            .map(|registry| {
+                registry
+                    .iter()
+                    .filter(|entry| entry.accessed.load(Ordering::Relaxed) <= idle)
+                    .map(|entry| entry.key().clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for key in keys {
+            self.evict(key);
+        }
+
+        Registry::cast_after(
+            Process::current(),
+            RegistryMessage::EvictIdle,
+            idle_interval(idle_timeout),
+        );
+    }
+
+    /// Sends an event to the event handler, if there is one.
+    fn event(&self, event: RegistryEvent) {
+        if let Some(event_handler) = &self.event_handler {
+            event_handler(event);
+        }
+    }
+
+    /// Removes every entry of a process that exited.
+    fn remove_entries(&mut self, pid: Pid) {
+        let Some(entries) = REGISTRY_ENTRIES.get(&self.name) else {
+            return;
+        };
+
+        let keys: Vec<RegistryKey> = entries
+            .iter()
+            .filter(|entry| entry.value().iter().any(|(process, _)| *process == pid))
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for key in keys {
+            remove_entries(&entries, &key, pid);
+        }
+    }
+
+    /// Sends our registry process to the registry on the given node.
+    fn discover(&self, node: Node) {
+        Registry::cast(
+            (self.name.clone(), node),
+            RegistryMessage::Discover(Process::current()),
+        );
+    }
+
+    /// Adds a peer registry to the ring, returns `true` if it's new.
+    fn peer_insert(&mut self, peer: Pid) -> bool {
+        if self.peers.contains_key(&peer) {
+            return false;
+        }
+
+        let Some(identity) = node_lookup_remote(peer.node()) else {
+            return false;
+        };
+
+        let node = Node::from(identity.clone());
+
+        self.peers
+            .insert(peer, (Process::monitor(peer), node.clone()));
+
+        registry_ring_add(&self.name, identity, node);
+
+        self.handoff();
+
+        true
+    }
+
+    /// Removes a peer registry that went down from the ring.
+    fn peer_down(&mut self, peer: Pid) {
+        let Some((_, node)) = self.peers.remove(&peer) else {
+            return;
+        };
+
+        if let Node::Remote(name, address) = node {
+            registry_ring_remove(&self.name, (name, address));
+        }
+    }
+
+    /// Removes the peer registry on a node that went down from the ring, so that the remaining nodes own it's keys right away.
+    fn node_down(&mut self, node: Node) {
+        self.peers.retain(|_, (monitor, peer)| {
+            if *peer == node {
+                Process::demonitor(*monitor);
+                return false;
+            }
+
+            true
+        });
+
+        if let Node::Remote(name, address) = node {
+            registry_ring_remove(&self.name, (name, address));
+        }
+    }
+
+    /// Exits the processes for keys that are now owned by another node.
+    fn handoff(&mut self) {
+        let keys: Vec<(Pid, RegistryKey)> = self
+            .lookup
+            .iter()
+            .filter(|(_, key)| registry_owner(&self.name, key).is_some_and(|node| node.is_remote()))
+            .map(|(process, key)| (*process, key.clone()))
+            .collect();
+
+        for (process, key) in keys {
+            #[cfg(feature = "tracing")]
+            tracing::info!(child_key = ?key, child_pid = ?process, "Handing off registered process");
+
+            Process::unlink(process);
+            Process::exit(process, ExitReason::from("handoff"));
+
+            self.lookup.remove(&process);
+
+            remove_process(&self.name, &key);
+
+            self.event(RegistryEvent::Removed(key, process));
+        }
     }
 }
 
 impl Drop for Registry {
     fn drop(&mut self) {
+        // A registry that failed to start must not remove the processes, ring, or entries of the running registry.
+        if !self.running {
+            return;
+        }
+
         REGISTRY.remove(&self.name);
 
+        if self.distributed {
+            REGISTRY_RINGS.remove(&self.name);
+        }
+
+        if self.duplicate_keys {
+            REGISTRY_ENTRIES.remove(&self.name);
+        }
+
         for process in self.lookup.keys() {
             Process::unlink(*process);
             Process::exit(*process, ExitReason::Kill);
@@ -456,6 +918,34 @@ impl GenServer for Registry {
     async fn init(&mut self) -> Result<(), ExitReason> {
         Process::set_flags(ProcessFlags::TRAP_EXIT);
 
+        self.running = true;
+
+        if self.duplicate_keys {
+            REGISTRY_ENTRIES.insert(self.name.clone(), DashMap::new());
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            Registry::cast_after(
+                Process::current(),
+                RegistryMessage::EvictIdle,
+                idle_interval(idle_timeout),
+            );
+        }
+
+        if self.distributed {
+            if let Some(identity) = node_lookup_local() {
+                REGISTRY_RINGS.insert(self.name.clone(), RegistryRing::new());
+
+                registry_ring_add(&self.name, identity, Node::Local);
+
+                Node::monitor_all();
+
+                for node in Node::list() {
+                    self.discover(node);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -468,9 +958,42 @@ impl GenServer for Registry {
 
         match message {
             RemoveLookup(process) => {
-                Process::unlink(process);
+                self.remove_lookup(process);
+                Ok(())
+            }
+            Discover(peer) => {
+                if self.peer_insert(peer) {
+                    Registry::cast(peer, Discover(Process::current()));
+                }
+
+                Ok(())
+            }
+            MonitorEntries(process) => {
+                self.entries
+                    .entry(process)
+                    .or_insert_with(|| Process::monitor(process));
+
+                Ok(())
+            }
+            DemonitorEntries(process) => {
+                // The process may have registered again since it asked to be demonitored.
+                let registered = REGISTRY_ENTRIES
+                    .get(&self.name)
+                    .is_some_and(|entries| has_entries(&entries, process));
+
+                if !registered {
+                    if let Some(monitor) = self.entries.remove(&process) {
+                        Process::demonitor(monitor);
+                    }
+                }
+
+                Ok(())
+            }
+            EvictIdle => {
+                if let Some(idle_timeout) = self.idle_timeout {
+                    self.evict_idle(idle_timeout);
+                }
 
-                self.lookup.remove(&process);
                 Ok(())
             }
             _ => unreachable!(),
@@ -527,6 +1050,23 @@ impl GenServer for Registry {
                 self.remove_process(pid, reason);
                 Ok(())
             }
+            Message::System(SystemMessage::ProcessDown(Dest::Pid(pid), _, _)) => {
+                if self.entries.remove(&pid).is_some() {
+                    self.remove_entries(pid);
+                } else {
+                    self.peer_down(pid);
+                }
+
+                Ok(())
+            }
+            Message::System(SystemMessage::NodeUp(node, _)) => {
+                self.discover(node);
+                Ok(())
+            }
+            Message::System(SystemMessage::NodeDown(node, _, _)) => {
+                self.node_down(node);
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
@@ -580,6 +1120,46 @@ impl std::convert::From<&str> for RegistryKey {
     }
 }
 
+impl std::convert::From<Vec<u8>> for RegistryKey {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+impl std::convert::From<&[u8]> for RegistryKey {
+    fn from(value: &[u8]) -> Self {
+        Self::Bytes(value.to_vec())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl std::convert::From<uuid::Uuid> for RegistryKey {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::Uuid(value)
+    }
+}
+
+impl<A, B> std::convert::From<(A, B)> for RegistryKey
+where
+    A: Into<RegistryKey>,
+    B: Into<RegistryKey>,
+{
+    fn from(value: (A, B)) -> Self {
+        Self::Tuple(vec![value.0.into(), value.1.into()])
+    }
+}
+
+impl<A, B, C> std::convert::From<(A, B, C)> for RegistryKey
+where
+    A: Into<RegistryKey>,
+    B: Into<RegistryKey>,
+    C: Into<RegistryKey>,
+{
+    fn from(value: (A, B, C)) -> Self {
+        Self::Tuple(vec![value.0.into(), value.1.into(), value.2.into()])
+    }
+}
+
 impl std::convert::From<CallError> for RegistryError {
     fn from(value: CallError) -> Self {
         Self::CallError(value)
@@ -588,16 +1168,19 @@ impl std::convert::From<CallError> for RegistryError {
 
 /// Looks up a process in the given registry assigned to the given key.
 fn lookup_process<T: AsRef<str>>(registry: T, key: &RegistryKey) -> Option<Pid> {
-    REGISTRY
-        .get(registry.as_ref())
-        .and_then(|registry| registry.get(key).map(|entry| *entry.value()))
+    REGISTRY.get(registry.as_ref()).and_then(|registry| {
+        registry.get(key).map(|entry| {
+            entry.accessed.store(registry_now(), Ordering::Relaxed);
+            entry.pid
+        })
+    })
 }
 
 /// Removes a process in the given registry assigned to the given key.
 fn remove_process<T: AsRef<str>>(registry: T, key: &RegistryKey) -> Option<Pid> {
     REGISTRY
         .get_mut(registry.as_ref())
-        .and_then(|registry| registry.remove(key).map(|entry| entry.1))
+        .and_then(|registry| registry.remove(key).map(|entry| entry.1.pid))
 }
 
 /// Counts the number of processes in a registry.
@@ -615,7 +1198,7 @@ fn list_processes<T: AsRef<str>>(registry: T) -> Vec<(RegistryKey, Pid)> {
         .map(|registry| {
             registry
                 .iter()
-                .map(|entry| (entry.key().clone(), *entry.value()))
+                .map(|entry| (entry.key().clone(), entry.pid))
                 .collect()
         })
         .unwrap_or_default()
@@ -623,8 +1206,82 @@ fn list_processes<T: AsRef<str>>(registry: T) -> Vec<(RegistryKey, Pid)> {
 
 /// Registers the given process in the local registry with the given key.
 fn register_process<T: Into<String>>(registry: T, key: RegistryKey, process: Pid) {
-    REGISTRY
-        .entry(registry.into())
-        .or_default()
-        .insert(key, process);
+    REGISTRY.entry(registry.into()).or_default().insert(
+        key,
+        RegistryEntry {
+            pid: process,
+            accessed: AtomicU64::new(registry_now()),
+        },
+    );
+}
+
+/// Returns the key of the process in a registry that was looked up the longest time ago.
+fn least_recent_process<T: AsRef<str>>(registry: T) -> Option<RegistryKey> {
+    REGISTRY.get(registry.as_ref()).and_then(|registry| {
+        registry
+            .iter()
+            .min_by_key(|entry| entry.accessed.load(Ordering::Relaxed))
+            .map(|entry| entry.key().clone())
+    })
+}
+
+/// Returns how often idle processes are checked for the given idle timeout.
+fn idle_interval(idle_timeout: Duration) -> Duration {
+    (idle_timeout / 2).max(Duration::from_millis(1))
+}
+
+/// Returns the number of milliseconds since the registry epoch.
+fn registry_now() -> u64 {
+    REGISTRY_EPOCH.elapsed().as_millis() as u64
+}
+
+/// Returns `true` if the given process has an entry under any key.
+fn has_entries(entries: &RegistryEntries, pid: Pid) -> bool {
+    entries
+        .iter()
+        .any(|entry| entry.value().iter().any(|(process, _)| *process == pid))
+}
+
+/// Removes every entry of the given process under the given key.
+fn remove_entries(entries: &RegistryEntries, key: &RegistryKey, pid: Pid) {
+    entries.alter(key, |_, mut values| {
+        values.retain(|(process, _)| *process != pid);
+        values
+    });
+
+    entries.remove_if(key, |_, values| values.is_empty());
+}
+
+/// Routes a request for the given key to the registry on the node that owns it, if the local registry is distributed.
+fn registry_route(registry: Dest, key: &RegistryKey) -> Dest {
+    if let Dest::Named(name, Node::Local) = &registry {
+        if let Some(owner) = registry_owner(name, key) {
+            if owner.is_remote() {
+                return Dest::Named(name.clone(), owner);
+            }
+        }
+    }
+
+    registry
+}
+
+/// Returns the node that owns the given key in a distributed registry.
+fn registry_owner<T: AsRef<str>>(registry: T, key: &RegistryKey) -> Option<Node> {
+    REGISTRY_RINGS
+        .get(registry.as_ref())
+        .and_then(|ring| ring.find_node(key))
+}
+
+/// Adds a node, identified by it's name and address, to the ring of a distributed registry.
+fn registry_ring_add<T: AsRef<str>>(registry: T, identity: (String, SocketAddr), node: Node) {
+    if let Some(ring) = REGISTRY_RINGS.get(registry.as_ref()) {
+        ring.add_node(&identity, node);
+    }
+}
+
+/// Removes a node, identified by it's name and address, from the ring of a distributed registry.
+fn registry_ring_remove<T: AsRef<str>>(registry: T, identity: (String, SocketAddr)) {
+    if let Some(ring) = REGISTRY_RINGS.get(registry.as_ref()) {
+        ring.remove_node(&identity);
+    }
 }
@@ -0,0 +1,13 @@
+use crate::Pid;
+use crate::RegistryKey;
+
+/// An event sent to the event handler of a [crate::Registry].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryEvent {
+    /// A process was started and registered under the key.
+    Registered(RegistryKey, Pid),
+    /// A process was removed because it exited, was stopped, or was removed.
+    Removed(RegistryKey, Pid),
+    /// A process was stopped because the registry was full, or the process was idle.
+    Evicted(RegistryKey, Pid),
+}
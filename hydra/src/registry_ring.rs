@@ -0,0 +1,114 @@
+use std::hash::BuildHasherDefault;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::net::SocketAddr;
+
+use serde::Serialize;
+
+use crate::frame::FRAME_CONFIG;
+
+use crate::HashRing;
+use crate::Node;
+use crate::RegistryKey;
+
+/// The number of points each node has on the ring.
+const REGISTRY_RING_POINTS: u64 = 16;
+
+/// The FNV-1a 64bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// The FNV-1a 64bit prime.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// A FNV-1a hasher with a murmur3 finalizer, which is specified, so that it never changes between platforms or compiler versions.
+#[derive(Clone, Copy)]
+struct RegistryHasher(u64);
+
+/// A canonical encoding of a ring key, which hashes only it's bytes.
+struct RegistryRingKey(Vec<u8>);
+
+/// The ring of nodes that own the keys of a distributed registry.
+///
+/// Keys and nodes are hashed over their bincode encoding, with fixed size little endian integers,
+/// so that every node agrees on the owner of a key.
+#[doc(hidden)]
+pub struct RegistryRing {
+    ring: HashRing<Node, BuildHasherDefault<RegistryHasher>>,
+}
+
+impl RegistryRing {
+    /// Constructs a new instance of [RegistryRing] with no nodes.
+    pub fn new() -> Self {
+        Self {
+            ring: HashRing::with_hasher(BuildHasherDefault::default()),
+        }
+    }
+
+    /// Adds a node, identified by it's name and address, to the ring.
+    pub fn add_node(&self, identity: &(String, SocketAddr), node: Node) {
+        self.ring.add_nodes(
+            (0..REGISTRY_RING_POINTS)
+                .map(|point| (RegistryRingKey::new(&(identity, point)), node.clone())),
+        );
+    }
+
+    /// Removes a node, identified by it's name and address, from the ring.
+    pub fn remove_node(&self, identity: &(String, SocketAddr)) {
+        for point in 0..REGISTRY_RING_POINTS {
+            self.ring
+                .remove_node(RegistryRingKey::new(&(identity, point)));
+        }
+    }
+
+    /// Returns the node that owns the given key, if there are any nodes in the ring.
+    pub fn find_node(&self, key: &RegistryKey) -> Option<Node> {
+        self.ring.find_node(RegistryRingKey::new(key))
+    }
+}
+
+impl Default for RegistryRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegistryRingKey {
+    /// Encodes the given value into a new instance of [RegistryRingKey].
+    fn new<T: Serialize>(value: &T) -> Self {
+        Self(bincode::serde::encode_to_vec(value, FRAME_CONFIG).unwrap())
+    }
+}
+
+impl Hash for RegistryRingKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.0);
+    }
+}
+
+impl Default for RegistryHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for RegistryHasher {
+    fn finish(&self) -> u64 {
+        // The murmur3 finalizer, so that similar keys spread across the whole ring.
+        let mut hash = self.0;
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^= hash >> 33;
+
+        hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
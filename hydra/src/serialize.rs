@@ -1,14 +1,46 @@
 use std::io;
+use std::sync::Arc;
 
-use serde::de::DeserializeOwned;
-use serde::Serialize;
+use crate::MessageFormat;
+use crate::Receivable;
 
-/// Serializes a value.
-pub fn serialize_value<T: Serialize>(value: &T) -> Vec<u8> {
-    rmp_serde::to_vec_named(value).unwrap()
+/// Serializes a value in the format for its type.
+pub fn serialize_value<T: Receivable>(value: &T) -> (MessageFormat, Arc<[u8]>) {
+    let format = MessageFormat::for_type::<T>();
+
+    let serialized = match format {
+        MessageFormat::MessagePack => rmp_serde::to_vec_named(value).unwrap(),
+        MessageFormat::MessagePackCompact => rmp_serde::to_vec(value).unwrap(),
+        MessageFormat::Bincode => {
+            bincode::serde::encode_to_vec(value, bincode::config::standard()).unwrap()
+        }
+        #[cfg(feature = "cbor")]
+        MessageFormat::Cbor => {
+            let mut serialized = Vec::new();
+
+            ciborium::into_writer(value, &mut serialized).unwrap();
+
+            serialized
+        }
+    };
+
+    (format, serialized.into())
 }
 
-/// Deserializes a value.
-pub fn deserialize_value<T: DeserializeOwned>(value: &[u8]) -> io::Result<T> {
-    rmp_serde::from_slice(value).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+/// Deserializes a value in the given format.
+pub fn deserialize_value<T: Receivable>(format: MessageFormat, value: &[u8]) -> io::Result<T> {
+    match format {
+        MessageFormat::MessagePack | MessageFormat::MessagePackCompact => {
+            rmp_serde::from_slice(value)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        }
+        MessageFormat::Bincode => {
+            bincode::serde::decode_from_slice(value, bincode::config::standard())
+                .map(|(value, _)| value)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        }
+        #[cfg(feature = "cbor")]
+        MessageFormat::Cbor => ciborium::from_reader(value)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+    }
 }
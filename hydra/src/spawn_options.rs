@@ -0,0 +1,54 @@
+use crate::ProcessFlags;
+
+/// Options used to spawn a process, which are applied before the process is first polled.
+#[derive(Debug, Default, Clone)]
+pub struct SpawnOptions {
+    pub(crate) name: Option<String>,
+    pub(crate) link: bool,
+    pub(crate) monitor: bool,
+    pub(crate) flags: ProcessFlags,
+    pub(crate) label: Option<String>,
+}
+
+impl SpawnOptions {
+    /// Constructs a new instance of [SpawnOptions] with the default values.
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            link: false,
+            monitor: false,
+            flags: ProcessFlags::empty(),
+            label: None,
+        }
+    }
+
+    /// Specifies a name to register the process under, the process isn't spawned if the name is taken.
+    pub fn name<T: Into<String>>(mut self, name: T) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Creates a link between the calling process and the new process. (Default false)
+    pub fn link(mut self, link: bool) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Creates a monitor of the new process for the calling process. (Default false)
+    pub fn monitor(mut self, monitor: bool) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Specifies the initial flags of the process. (Default empty)
+    pub fn flags(mut self, flags: ProcessFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Specifies a label to describe the process.
+    pub fn label<T: Into<String>>(mut self, label: T) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
@@ -6,6 +6,7 @@ use std::time::Instant;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::process_set_child_id;
 use crate::shutdown_brutal_kill;
 use crate::shutdown_infinity;
 use crate::shutdown_timeout;
@@ -670,6 +671,10 @@ impl Supervisor {
                 #[cfg(feature = "tracing")]
                 tracing::info!(child_id = ?child.spec.id, child_pid = ?pid, "Started child");
 
+                if pid.is_local() {
+                    process_set_child_id(pid, &child.spec.id);
+                }
+
                 Ok(Some(pid))
             }
             Err(reason) => {
@@ -874,7 +879,8 @@ impl GenServer for Supervisor {
     type Message = SupervisorMessage;
 
     async fn init(&mut self) -> Result<(), ExitReason> {
-        Process::set_flags(ProcessFlags::TRAP_EXIT);
+        // Already set when started with SupervisorOptions, but not when started through GenServer directly.
+        Process::set_flags(Process::flags() | ProcessFlags::TRAP_EXIT);
 
         self.init_children().await
     }
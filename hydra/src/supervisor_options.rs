@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use crate::GenServerOptions;
+use crate::ProcessFlags;
 
 /// Options used to configure a Supervisor.
 #[derive(Debug, Default, Clone)]
@@ -43,6 +44,8 @@ impl From<SupervisorOptions> for GenServerOptions {
             options = options.timeout(timeout);
         }
 
+        options.flags = ProcessFlags::TRAP_EXIT;
+
         options
     }
 }
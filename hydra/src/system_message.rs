@@ -11,6 +11,8 @@ pub enum SystemMessage {
     Exit(Pid, ExitReason),
     /// A monitored process went down.
     ProcessDown(Dest, Reference, ExitReason),
-    /// A monitored node went down.
-    NodeDown(Node, Reference),
+    /// A monitored node went down with the given reason.
+    NodeDown(Node, Reference, ExitReason),
+    /// A node came up for a monitor installed on every node.
+    NodeUp(Node, Reference),
 }
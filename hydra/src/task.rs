@@ -1,15 +1,42 @@
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use futures_util::FutureExt;
+use futures_util::Stream;
+
+use serde::Deserialize;
+use serde::Serialize;
 
 use tokio::task::JoinHandle;
 
+use crate::AsyncStreamOptions;
+use crate::ExitReason;
+use crate::Message;
+use crate::Pid;
+use crate::Process;
+use crate::Receivable;
+use crate::Reference;
+use crate::SpawnOptions;
+use crate::SystemMessage;
+use crate::TaskStream;
+use crate::Timeout;
+
+/// Unique message type for an [AsyncTask] run signal and reply.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum TaskMessage<R> {
+    #[serde(rename = "$task_run")]
+    Run,
+    #[serde(rename = "$task_reply")]
+    Reply(Pid, R),
+}
+
 /// A task is a lightweight thread of execution designed to run one particular action.
 ///
-/// Tasks can not receive messages from other processes, only send.
+/// Tasks spawned with `spawn` can not receive messages from other processes, only send.
 ///
 /// Calls that are allowed:
 /// - Process::send
@@ -24,6 +51,9 @@ use tokio::task::JoinHandle;
 ///
 /// It's not recommended to `await` long running tasks in a `GenServer` since it will delay processing of other messages.
 /// Instead, you should send a `cast` with the result of your task and handle it in `handle_cast`.
+///
+/// Tasks spawned with `async_link` or `async_nolink` run in their own process, which can be linked, monitored, and supervised.
+/// The result is sent to the process that started the task, and received with `await_async`, or `yield_async`.
 pub struct Task;
 
 /// The result of a spawned task, can be used to await the task result, or shutdown the task.
@@ -32,9 +62,23 @@ pub struct TaskHandle<R> {
     handle: JoinHandle<R>,
 }
 
+/// A task running in it's own process, which sends it's result to the process that started it.
+pub struct AsyncTask<R> {
+    pid: Pid,
+    monitor: Reference,
+    _result: PhantomData<fn() -> R>,
+}
+
 /// An error occured while executing the task.
 #[derive(Debug)]
-pub struct TaskError(pub String);
+pub enum TaskError {
+    /// The task was cancelled, or panicked while running.
+    Join(String),
+    /// The process running the task exited before replying.
+    Exit(ExitReason),
+    /// The task didn't reply within the timeout.
+    Timeout,
+}
 
 impl Task {
     /// Runs the provided asynchronous `task`.
@@ -61,6 +105,122 @@ impl Task {
         }
     }
 
+    /// Runs the provided asynchronous `task` in a new process that is linked to, and monitored by the current process.
+    ///
+    /// If the task crashes, the current process exits with the same reason unless it traps exits.
+    pub fn async_link<F>(task: F) -> AsyncTask<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Receivable,
+    {
+        spawn_async_task(task, SpawnOptions::new().link(true).monitor(true))
+    }
+
+    /// Runs the provided asynchronous `task` in a new process that is monitored by the current process.
+    ///
+    /// If the task crashes, the reason is returned when the task is awaited.
+    pub fn async_nolink<F>(task: F) -> AsyncTask<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Receivable,
+    {
+        spawn_async_task(task, SpawnOptions::new().monitor(true))
+    }
+
+    /// Waits for the result of the given process task.
+    ///
+    /// Returns [TaskError] if the task exits before replying, or it doesn't reply within the timeout, in which case the task is unlinked, and killed.
+    ///
+    /// The default timeout is 5000ms.
+    pub async fn await_async<R: Receivable>(
+        task: AsyncTask<R>,
+        timeout: Option<Duration>,
+    ) -> Result<R, TaskError> {
+        let timeout = timeout.unwrap_or(Duration::from_millis(5000));
+        let Ok(result) = receive_async_task(&task, Some(timeout)).await else {
+            // Waiting for the task to exit consumes a reply sent before it was killed.
+            let _ = kill_async_task(&task).await;
+
+            return Err(TaskError::Timeout);
+        };
+
+        Process::demonitor(task.monitor);
+
+        result.map_err(TaskError::from)
+    }
+
+    /// Waits for the result of the given process task for up to the given timeout.
+    ///
+    /// Returns [None] if the task is still running, in which case it can be yielded again.
+    pub async fn yield_async<R: Receivable>(
+        task: &AsyncTask<R>,
+        timeout: Duration,
+    ) -> Option<Result<R, TaskError>> {
+        let result = receive_async_task(task, Some(timeout)).await.ok()?;
+
+        Process::demonitor(task.monitor);
+
+        Some(result.map_err(TaskError::from))
+    }
+
+    /// Kills the given process task, and then checks for a result.
+    ///
+    /// Returns the result if the task replied before it was killed, [None] if it was killed, or [TaskError] if it exited for another reason.
+    pub async fn shutdown_async<R: Receivable>(task: AsyncTask<R>) -> Option<Result<R, TaskError>> {
+        match kill_async_task(&task).await {
+            Ok(result) => Some(Ok(result)),
+            Err(reason) if reason.is_kill() => None,
+            Err(reason) => Some(Err(TaskError::from(reason))),
+        }
+    }
+
+    /// Runs `fun` over each item of the given iterator, in a new process per item, that is monitored by the current process.
+    ///
+    /// Returns a stream of the results, which must be consumed by the current process, and kills any running tasks when dropped. See [AsyncStreamOptions] for concurrency, ordering, and timeouts.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let stream = Task::async_stream(urls, |url| fetch(url), AsyncStreamOptions::new().max_concurrency(8));
+    ///
+    /// let results: Vec<_> = stream.collect().await;
+    /// ```
+    pub fn async_stream<I, F, Fut>(
+        items: I,
+        fun: F,
+        options: AsyncStreamOptions,
+    ) -> impl Stream<Item = Result<Fut::Output, TaskError>>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Receivable,
+    {
+        Self::async_stream_from(futures_util::stream::iter(items), fun, options)
+    }
+
+    /// Runs `fun` over each item of the given stream, in a new process per item, that is monitored by the current process.
+    ///
+    /// See [Task::async_stream] for details.
+    pub fn async_stream_from<S, F, Fut>(
+        stream: S,
+        fun: F,
+        options: AsyncStreamOptions,
+    ) -> impl Stream<Item = Result<Fut::Output, TaskError>>
+    where
+        S: Stream,
+        F: FnMut(S::Item) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Receivable,
+    {
+        let stream = TaskStream::new(stream, fun, options);
+
+        futures_util::stream::unfold(stream, |mut stream| async move {
+            let result = stream.next().await?;
+
+            Some((result, stream))
+        })
+    }
+
     /// Shuts down the task, and then checks for a result.
     ///
     /// Returns the result if the task finishes while shutting down, [TaskError] if the task died before returning.
@@ -68,7 +228,7 @@ impl Task {
         task.handle.abort();
         task.handle
             .await
-            .map_err(|error| TaskError(error.to_string()))
+            .map_err(|error| TaskError::Join(error.to_string()))
     }
 
     /// Await many tasks at once and returns their results in order, or returns the first error that occurs.
@@ -84,7 +244,7 @@ impl Task {
             results.push(
                 task.handle
                     .await
-                    .map_err(|error| TaskError(error.to_string()))?,
+                    .map_err(|error| TaskError::Join(error.to_string()))?,
             );
         }
 
@@ -101,6 +261,121 @@ where
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         self.handle
             .poll_unpin(cx)
-            .map_err(|error| TaskError(error.to_string()))
+            .map_err(|error| TaskError::Join(error.to_string()))
+    }
+}
+
+impl<R> AsyncTask<R> {
+    /// Constructs a new [AsyncTask] for the given process, and monitor.
+    pub(crate) const fn new(pid: Pid, monitor: Reference) -> Self {
+        Self {
+            pid,
+            monitor,
+            _result: PhantomData,
+        }
+    }
+
+    /// Returns the [Pid] of the process running the task.
+    pub const fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// Returns the monitor [Reference] of the process running the task.
+    pub const fn monitor(&self) -> Reference {
+        self.monitor
+    }
+}
+
+impl From<ExitReason> for TaskError {
+    fn from(value: ExitReason) -> Self {
+        Self::Exit(value)
+    }
+}
+
+/// Spawns a process that runs the given task, and sends the result to the current process.
+pub(crate) fn spawn_async_task<F>(task: F, options: SpawnOptions) -> AsyncTask<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Receivable,
+{
+    let owner = Process::current();
+
+    match Process::spawn_opt(run_async_task(owner, task), options) {
+        Ok((pid, Some(monitor))) => AsyncTask::new(pid, monitor),
+        _ => unreachable!(),
+    }
+}
+
+/// Runs the given task, and sends the result to the owner process.
+pub(crate) async fn run_async_task<F>(owner: Pid, task: F)
+where
+    F: Future,
+    F::Output: Receivable,
+{
+    let result = task.await;
+
+    Process::send(owner, TaskMessage::Reply(Process::current(), result));
+}
+
+/// Waits for the owner of the current task to link, and monitor it before it runs.
+///
+/// Returns `false` if the owner went down first, in which case the task must not run.
+pub(crate) async fn wait_async_task(owner: Pid) -> bool {
+    let monitor = Process::monitor(owner);
+
+    let message = Process::receiver()
+        .for_message::<TaskMessage<()>>()
+        .select(|message| match message {
+            Message::User(TaskMessage::Run) => true,
+            Message::System(SystemMessage::ProcessDown(_, reference, _)) => *reference == monitor,
+            _ => false,
+        })
+        .await;
+
+    Process::demonitor(monitor);
+
+    matches!(message, Message::User(TaskMessage::Run))
+}
+
+/// Unlinks, and kills the given process task, then waits for it to exit, returning a reply sent before it was killed.
+pub(crate) async fn kill_async_task<R: Receivable>(task: &AsyncTask<R>) -> Result<R, ExitReason> {
+    Process::unlink(task.pid);
+    Process::exit(task.pid, ExitReason::Kill);
+
+    let result = receive_async_task(task, None).await;
+
+    Process::demonitor(task.monitor);
+
+    match result {
+        Ok(result) => result,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Receives the result, or the exit reason of the given process task.
+async fn receive_async_task<R: Receivable>(
+    task: &AsyncTask<R>,
+    timeout: Option<Duration>,
+) -> Result<Result<R, ExitReason>, Timeout> {
+    let pid = task.pid;
+    let monitor = task.monitor;
+
+    let receiver = Process::receiver()
+        .for_message::<TaskMessage<R>>()
+        .select(|message| match message {
+            Message::User(TaskMessage::Reply(from, _)) => *from == pid,
+            Message::System(SystemMessage::ProcessDown(_, tag, _)) => *tag == monitor,
+            _ => false,
+        });
+
+    let message = match timeout {
+        Some(duration) => Process::timeout(duration, receiver).await?,
+        None => receiver.await,
+    };
+
+    match message {
+        Message::User(TaskMessage::Reply(_, result)) => Ok(Ok(result)),
+        Message::System(SystemMessage::ProcessDown(_, _, reason)) => Ok(Err(reason)),
+        _ => unreachable!(),
     }
 }
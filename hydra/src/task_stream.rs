@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use std::time::Instant;
+
+use futures_util::Stream;
+use futures_util::StreamExt;
+
+use crate::alias_destroy;
+use crate::kill_async_task;
+use crate::monitor_destroy;
+use crate::process_exit;
+use crate::spawn_async_task;
+use crate::AsyncStreamOptions;
+use crate::AsyncTask;
+use crate::ExitReason;
+use crate::Message;
+use crate::OnTimeout;
+use crate::Pid;
+use crate::Process;
+use crate::Receivable;
+use crate::SpawnOptions;
+use crate::SystemMessage;
+use crate::TaskError;
+use crate::TaskMessage;
+use crate::PROCESS;
+
+/// A task running for an item of the stream.
+struct TaskStreamEntry<R> {
+    index: u64,
+    task: AsyncTask<R>,
+    deadline: Instant,
+}
+
+/// Runs a function over each item of a stream in a process, with a limited number of processes at a time.
+///
+/// The tasks are monitored by, and reply to the process that created the stream, so it must be polled from that process.
+pub(crate) struct TaskStream<S, F, R: Receivable> {
+    owner: Pid,
+    input: Option<Pin<Box<S>>>,
+    fun: F,
+    max_concurrency: usize,
+    unordered: bool,
+    timeout: Duration,
+    on_timeout: OnTimeout,
+    running: Vec<TaskStreamEntry<R>>,
+    completed: BTreeMap<u64, Result<R, TaskError>>,
+    next_input: u64,
+    next_output: u64,
+}
+
+impl<S, F, Fut, R> TaskStream<S, F, R>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+    R: Receivable,
+{
+    /// Constructs a new [TaskStream] for the given input, function, and options.
+    pub fn new(input: S, fun: F, options: AsyncStreamOptions) -> Self {
+        let max_concurrency = options.max_concurrency.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|count| count.get())
+                .unwrap_or(1)
+        });
+
+        Self {
+            owner: Process::current(),
+            input: Some(Box::pin(input)),
+            fun,
+            max_concurrency,
+            unordered: options.unordered,
+            timeout: options.timeout.unwrap_or(Duration::from_millis(5000)),
+            on_timeout: options.on_timeout,
+            running: Vec::new(),
+            completed: BTreeMap::new(),
+            next_input: 0,
+            next_output: 0,
+        }
+    }
+
+    /// Returns the next result, starting tasks for new items as others complete.
+    pub async fn next(&mut self) -> Option<Result<R, TaskError>> {
+        if Process::current() != self.owner {
+            panic!("Can't poll an async stream from a process other than the one that created it!");
+        }
+
+        loop {
+            if let Some(result) = self.pop_completed() {
+                return Some(result);
+            }
+
+            while self.running.len() < self.max_concurrency {
+                let Some(input) = self.input.as_mut() else {
+                    break;
+                };
+
+                match input.next().await {
+                    Some(item) => self.spawn(item),
+                    None => self.input = None,
+                }
+            }
+
+            if self.running.is_empty() {
+                // Every task has completed, so there are no gaps left in the ordered results.
+                return self.completed.pop_first().map(|(_, result)| result);
+            }
+
+            self.receive().await;
+        }
+    }
+
+    /// Starts a task for the given item.
+    fn spawn(&mut self, item: S::Item) {
+        let task = spawn_async_task((self.fun)(item), SpawnOptions::new().monitor(true));
+
+        self.running.push(TaskStreamEntry {
+            index: self.next_input,
+            task,
+            deadline: Instant::now() + self.timeout,
+        });
+
+        self.next_input += 1;
+    }
+
+    /// Returns the next result that can be emitted, if any.
+    fn pop_completed(&mut self) -> Option<Result<R, TaskError>> {
+        if self.unordered {
+            return self.completed.pop_first().map(|(_, result)| result);
+        }
+
+        let result = self.completed.remove(&self.next_output)?;
+
+        self.next_output += 1;
+
+        Some(result)
+    }
+
+    /// Waits for a running task to reply, exit, or time out.
+    async fn receive(&mut self) {
+        let deadline = self
+            .running
+            .iter()
+            .map(|entry| entry.deadline)
+            .min()
+            .unwrap_or_else(Instant::now);
+
+        let running = &self.running;
+
+        let receiver = Process::receiver()
+            .for_message::<TaskMessage<R>>()
+            .select(|message| match message {
+                Message::User(TaskMessage::Reply(pid, _)) => {
+                    running.iter().any(|entry| entry.task.pid() == *pid)
+                }
+                Message::System(SystemMessage::ProcessDown(_, tag, _)) => {
+                    running.iter().any(|entry| entry.task.monitor() == *tag)
+                }
+                _ => false,
+            });
+
+        let result =
+            Process::timeout(deadline.saturating_duration_since(Instant::now()), receiver).await;
+
+        match result {
+            Ok(Message::User(TaskMessage::Reply(pid, result))) => {
+                if let Some(index) = self
+                    .running
+                    .iter()
+                    .position(|entry| entry.task.pid() == pid)
+                {
+                    let entry = self.running.swap_remove(index);
+
+                    Process::demonitor(entry.task.monitor());
+
+                    self.completed.insert(entry.index, Ok(result));
+                }
+            }
+            Ok(Message::System(SystemMessage::ProcessDown(_, tag, reason))) => {
+                if let Some(index) = self
+                    .running
+                    .iter()
+                    .position(|entry| entry.task.monitor() == tag)
+                {
+                    let entry = self.running.swap_remove(index);
+
+                    self.completed
+                        .insert(entry.index, Err(TaskError::from(reason)));
+                }
+            }
+            Ok(_) => unreachable!(),
+            Err(_) => self.expire().await,
+        }
+    }
+
+    /// Handles every task that has run past it's deadline.
+    async fn expire(&mut self) {
+        if self.on_timeout == OnTimeout::Exit {
+            self.kill_all();
+
+            Process::exit(Process::current(), ExitReason::from("timeout"));
+
+            // The current process is aborted on the next yield.
+            return std::future::pending().await;
+        }
+
+        let now = Instant::now();
+        let mut index = 0;
+
+        while index < self.running.len() {
+            if self.running[index].deadline > now {
+                index += 1;
+                continue;
+            }
+
+            let entry = self.running.swap_remove(index);
+
+            // Waiting for the task to exit consumes a reply sent before it was killed.
+            let _ = kill_async_task(&entry.task).await;
+
+            self.completed.insert(entry.index, Err(TaskError::Timeout));
+        }
+    }
+}
+
+impl<S, F, R: Receivable> TaskStream<S, F, R> {
+    /// Kills every running task.
+    fn kill_all(&mut self) {
+        for entry in self.running.drain(..) {
+            kill_task(&entry.task, self.owner);
+        }
+    }
+}
+
+impl<S, F, R: Receivable> Drop for TaskStream<S, F, R> {
+    fn drop(&mut self) {
+        self.kill_all();
+    }
+}
+
+/// Kills the given task, and drops a reply that may already be in the owner's message inbox.
+///
+/// When the stream is dropped outside of the owner process, only the task side of the monitor can be removed.
+fn kill_task<R: Receivable>(task: &AsyncTask<R>, owner: Pid) {
+    let pid = task.pid();
+
+    if PROCESS.try_with(|process| process.pid) != Ok(owner) {
+        monitor_destroy(pid, task.monitor());
+        alias_destroy(task.monitor());
+        process_exit(pid, owner, ExitReason::Kill);
+        return;
+    }
+
+    Process::demonitor(task.monitor());
+    Process::exit(pid, ExitReason::Kill);
+
+    Process::receiver().for_message::<TaskMessage<R>>().remove(
+        |message| matches!(message, Message::User(TaskMessage::Reply(from, _)) if *from == pid),
+    );
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::run_async_task;
+use crate::wait_async_task;
+use crate::AsyncTask;
+use crate::CallError;
+use crate::ChildSpec;
+use crate::ChildType;
+use crate::Dest;
+use crate::ExitReason;
+use crate::From;
+use crate::GenServer;
+use crate::GenServerOptions;
+use crate::Local;
+use crate::Message;
+use crate::Pid;
+use crate::Process;
+use crate::ProcessFlags;
+use crate::Receivable;
+use crate::SystemMessage;
+use crate::TaskMessage;
+
+/// A boxed task future, which is spawned as a supervised process.
+type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Builds a task future from a serialized remote task, and the process to reply to.
+type RemoteTaskStart = Arc<dyn Fn(&[u8], Option<Pid>) -> Option<TaskFuture> + Send + Sync>;
+
+/// A task supervisor message.
+#[doc(hidden)]
+#[derive(Serialize, Deserialize)]
+pub enum TaskSupervisorMessage {
+    Start(Local<TaskFuture>, Option<Pid>),
+    StartRemote(String, Vec<u8>, Option<Pid>),
+    StartSuccess(Pid),
+    StartError(TaskSupervisorError),
+    Children,
+    ChildrenSuccess(Vec<Pid>),
+}
+
+/// Errors for [TaskSupervisor] calls.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TaskSupervisorError {
+    /// A call to the [TaskSupervisor] server has failed.
+    CallError(CallError),
+    /// The remote task wasn't registered with the supervisor.
+    UnknownTask(String),
+    /// The remote task failed to deserialize.
+    InvalidTask(String),
+}
+
+/// A serializable task that can be started by a [TaskSupervisor] on any node.
+///
+/// ## Example
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Resize {
+///     path: String,
+/// }
+///
+/// impl RemoteTask for Resize {
+///     const NAME: &'static str = "resize";
+///
+///     type Output = bool;
+///
+///     async fn run(self) -> Self::Output {
+///         resize_image(self.path).await
+///     }
+/// }
+///
+/// let task = TaskSupervisor::async_nolink_remote(("tasks", node), Resize { path }).await?;
+///
+/// Task::await_async(task, None).await?;
+/// ```
+pub trait RemoteTask: Receivable {
+    /// The name the task is registered under, which must be the same on every node.
+    const NAME: &'static str;
+
+    /// The result of the task.
+    type Output: Receivable;
+
+    /// Runs the task.
+    fn run(self) -> impl Future<Output = Self::Output> + Send;
+}
+
+/// A supervisor that starts tasks as processes, on the local node, or on remote nodes.
+///
+/// Tasks are linked to the supervisor, so they are shut down with the supervisor, while task crashes are ignored by the supervisor.
+#[derive(Clone, Default)]
+pub struct TaskSupervisor {
+    remote_tasks: BTreeMap<String, RemoteTaskStart>,
+    children: BTreeSet<Pid>,
+}
+
+impl TaskSupervisor {
+    /// Constructs a new [TaskSupervisor].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a [RemoteTask] that can be started on this supervisor from any node.
+    pub fn remote_task<T: RemoteTask>(mut self) -> Self {
+        let start: RemoteTaskStart = Arc::new(|task, owner| {
+            let task: T = rmp_serde::from_slice(task).ok()?;
+
+            let future: TaskFuture = match owner {
+                Some(owner) => Box::pin(run_async_task(owner, task.run())),
+                None => Box::pin(async move {
+                    let _ = task.run().await;
+                }),
+            };
+
+            Some(future)
+        });
+
+        self.remote_tasks.insert(String::from(T::NAME), start);
+        self
+    }
+
+    /// Create a task supervisor process not linked to a supervision tree.
+    pub async fn start(self, options: GenServerOptions) -> Result<Pid, ExitReason> {
+        GenServer::start(self, options).await
+    }
+
+    /// Creates a task supervisor process as part of a supervision tree.
+    pub async fn start_link(self, options: GenServerOptions) -> Result<Pid, ExitReason> {
+        GenServer::start_link(self, options).await
+    }
+
+    /// Builds a child specification for this [TaskSupervisor] process.
+    pub fn child_spec(self, options: GenServerOptions) -> ChildSpec {
+        ChildSpec::new("TaskSupervisor")
+            .start(move || self.clone().start_link(options.clone()))
+            .child_type(ChildType::Supervisor)
+    }
+
+    /// Starts the given task as a child of the given local supervisor, without a result.
+    pub async fn start_child<D, F>(supervisor: D, task: F) -> Result<Pid, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        use TaskSupervisorMessage::*;
+
+        match TaskSupervisor::call(supervisor, Start(Local::new(Box::pin(task)), None), None)
+            .await?
+        {
+            StartSuccess(pid) => Ok(pid),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Starts the given task as a child of the given local supervisor, linked to, and monitored by the current process.
+    pub async fn async_link<D, F>(
+        supervisor: D,
+        task: F,
+    ) -> Result<AsyncTask<F::Output>, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        F: Future + Send + 'static,
+        F::Output: Receivable,
+    {
+        let task = Box::pin(run_async_task(Process::current(), task));
+
+        start_async(supervisor.into(), Local::new(task), true).await
+    }
+
+    /// Starts the given task as a child of the given local supervisor, monitored by the current process.
+    pub async fn async_nolink<D, F>(
+        supervisor: D,
+        task: F,
+    ) -> Result<AsyncTask<F::Output>, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        F: Future + Send + 'static,
+        F::Output: Receivable,
+    {
+        let task = Box::pin(run_async_task(Process::current(), task));
+
+        start_async(supervisor.into(), Local::new(task), false).await
+    }
+
+    /// Starts the given remote task as a child of the given supervisor, which may live on any node, without a result.
+    pub async fn start_child_remote<D, T>(
+        supervisor: D,
+        task: T,
+    ) -> Result<Pid, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        T: RemoteTask,
+    {
+        use TaskSupervisorMessage::*;
+
+        let message = StartRemote(String::from(T::NAME), serialize_task(&task)?, None);
+
+        match TaskSupervisor::call(supervisor, message, None).await? {
+            StartSuccess(pid) => Ok(pid),
+            StartError(error) => Err(error),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Starts the given remote task as a child of the given supervisor, which may live on any node, linked to, and monitored by the current process.
+    pub async fn async_link_remote<D, T>(
+        supervisor: D,
+        task: T,
+    ) -> Result<AsyncTask<T::Output>, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        T: RemoteTask,
+    {
+        start_async_remote(supervisor.into(), task, true).await
+    }
+
+    /// Starts the given remote task as a child of the given supervisor, which may live on any node, monitored by the current process.
+    pub async fn async_nolink_remote<D, T>(
+        supervisor: D,
+        task: T,
+    ) -> Result<AsyncTask<T::Output>, TaskSupervisorError>
+    where
+        D: Into<Dest>,
+        T: RemoteTask,
+    {
+        start_async_remote(supervisor.into(), task, false).await
+    }
+
+    /// Returns the tasks that are currently running under the given supervisor.
+    pub async fn children<D: Into<Dest>>(supervisor: D) -> Result<Vec<Pid>, TaskSupervisorError> {
+        use TaskSupervisorMessage::*;
+
+        match TaskSupervisor::call(supervisor, Children, None).await? {
+            ChildrenSuccess(children) => Ok(children),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Spawns the given task as a child of this supervisor, which waits for the given owner to signal it to run.
+    fn spawn_task(&mut self, task: TaskFuture, owner: Option<Pid>) -> Pid {
+        let pid = Process::spawn_link(async move {
+            if let Some(owner) = owner {
+                if !wait_async_task(owner).await {
+                    return;
+                }
+            }
+
+            task.await
+        });
+
+        self.children.insert(pid);
+
+        pid
+    }
+}
+
+impl GenServer for TaskSupervisor {
+    type Message = TaskSupervisorMessage;
+
+    async fn init(&mut self) -> Result<(), ExitReason> {
+        Process::set_flags(ProcessFlags::TRAP_EXIT);
+
+        Ok(())
+    }
+
+    async fn handle_call(
+        &mut self,
+        message: Self::Message,
+        _from: From,
+    ) -> Result<Option<Self::Message>, ExitReason> {
+        use TaskSupervisorMessage::*;
+
+        match message {
+            Start(task, owner) => Ok(Some(StartSuccess(
+                self.spawn_task(task.into_inner(), owner),
+            ))),
+            StartRemote(name, task, owner) => {
+                let Some(start) = self.remote_tasks.get(&name) else {
+                    return Ok(Some(StartError(TaskSupervisorError::UnknownTask(name))));
+                };
+
+                let Some(task) = start(&task, owner) else {
+                    return Ok(Some(StartError(TaskSupervisorError::InvalidTask(name))));
+                };
+
+                Ok(Some(StartSuccess(self.spawn_task(task, owner))))
+            }
+            Children => Ok(Some(ChildrenSuccess(Vec::from_iter(
+                self.children.iter().copied(),
+            )))),
+            _ => unreachable!(),
+        }
+    }
+
+    async fn handle_info(&mut self, info: Message<Self::Message>) -> Result<(), ExitReason> {
+        if let Message::System(SystemMessage::Exit(pid, _)) = info {
+            self.children.remove(&pid);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::convert::From<CallError> for TaskSupervisorError {
+    fn from(value: CallError) -> Self {
+        Self::CallError(value)
+    }
+}
+
+/// Starts the given task on the given supervisor, then links, and monitors it before it runs.
+async fn start_async<R: Receivable>(
+    supervisor: Dest,
+    task: Local<TaskFuture>,
+    link: bool,
+) -> Result<AsyncTask<R>, TaskSupervisorError> {
+    use TaskSupervisorMessage::*;
+
+    match TaskSupervisor::call(supervisor, Start(task, Some(Process::current())), None).await? {
+        StartSuccess(pid) => Ok(run_async(pid, link)),
+        _ => unreachable!(),
+    }
+}
+
+/// Starts the given remote task on the given supervisor, then links, and monitors it before it runs.
+async fn start_async_remote<T: RemoteTask>(
+    supervisor: Dest,
+    task: T,
+    link: bool,
+) -> Result<AsyncTask<T::Output>, TaskSupervisorError> {
+    use TaskSupervisorMessage::*;
+
+    let message = StartRemote(
+        String::from(T::NAME),
+        serialize_task(&task)?,
+        Some(Process::current()),
+    );
+
+    match TaskSupervisor::call(supervisor, message, None).await? {
+        StartSuccess(pid) => Ok(run_async(pid, link)),
+        StartError(error) => Err(error),
+        _ => unreachable!(),
+    }
+}
+
+/// Links, and monitors the given task, then signals it to run.
+fn run_async<R>(pid: Pid, link: bool) -> AsyncTask<R> {
+    if link {
+        Process::link(pid);
+    }
+
+    let monitor = Process::monitor(pid);
+
+    Process::send(pid, TaskMessage::<()>::Run);
+
+    AsyncTask::new(pid, monitor)
+}
+
+/// Serializes the given remote task.
+fn serialize_task<T: RemoteTask>(task: &T) -> Result<Vec<u8>, TaskSupervisorError> {
+    rmp_serde::to_vec_named(task)
+        .map_err(|_| TaskSupervisorError::InvalidTask(String::from(T::NAME)))
+}
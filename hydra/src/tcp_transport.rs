@@ -0,0 +1,69 @@
+use std::io;
+use std::net::SocketAddr;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+use crate::NodeListener;
+use crate::NodeStream;
+use crate::NodeTransport;
+
+/// The default transport, which connects nodes over tcp.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TcpTransport;
+
+impl TcpTransport {
+    /// Constructs a new instance of [TcpTransport].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl NodeTransport for TcpTransport {
+    fn listen(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeListener>>> {
+        async move {
+            let listener = TcpListener::bind(address).await?;
+
+            Ok(Box::new(listener) as Box<dyn NodeListener>)
+        }
+        .boxed()
+    }
+
+    fn connect(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeStream>>> {
+        async move {
+            let socket = TcpStream::connect(address).await?;
+
+            tcp_set_nodelay(&socket);
+
+            Ok(Box::new(socket) as Box<dyn NodeStream>)
+        }
+        .boxed()
+    }
+}
+
+impl NodeListener for TcpListener {
+    fn accept(&mut self) -> BoxFuture<'_, io::Result<Box<dyn NodeStream>>> {
+        async move {
+            let (socket, _) = TcpListener::accept(self).await?;
+
+            tcp_set_nodelay(&socket);
+
+            Ok(Box::new(socket) as Box<dyn NodeStream>)
+        }
+        .boxed()
+    }
+}
+
+/// Disables nagle's algorithm on the socket, frames are already buffered by the codec.
+fn tcp_set_nodelay(socket: &TcpStream) {
+    if let Err(error) = socket.set_nodelay(true) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = ?error, "Failed to set TCP_NODELAY on socket");
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = error;
+    }
+}
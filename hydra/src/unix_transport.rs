@@ -0,0 +1,93 @@
+use std::fmt::Debug;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use tokio::net::UnixListener;
+use tokio::net::UnixStream;
+
+use crate::NodeListener;
+use crate::NodeStream;
+use crate::NodeTransport;
+
+/// A transport that connects nodes on the same host over unix domain sockets.
+///
+/// Nodes are still identified by their broadcast address, the caller decides which socket file each address maps to.
+///
+/// ## Example
+/// ```ignore
+/// let transport = UnixTransport::new(|address| PathBuf::from(format!("/tmp/hydra/{}.sock", address.port())));
+/// ```
+#[derive(Clone)]
+pub struct UnixTransport {
+    path: Arc<dyn Fn(SocketAddr) -> PathBuf + Send + Sync>,
+}
+
+impl UnixTransport {
+    /// Constructs a new instance of [UnixTransport] which uses the socket file returned by `path` for each address.
+    pub fn new<F>(path: F) -> Self
+    where
+        F: Fn(SocketAddr) -> PathBuf + Send + Sync + 'static,
+    {
+        Self {
+            path: Arc::new(path),
+        }
+    }
+
+    /// Returns the socket path used for the given address.
+    pub fn path(&self, address: SocketAddr) -> PathBuf {
+        (self.path)(address)
+    }
+}
+
+impl Debug for UnixTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnixTransport").finish_non_exhaustive()
+    }
+}
+
+impl NodeTransport for UnixTransport {
+    fn listen(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeListener>>> {
+        let path = self.path(address);
+
+        async move {
+            // A socket file left behind by a previous run would prevent binding.
+            if let Err(error) = std::fs::remove_file(&path) {
+                if error.kind() != io::ErrorKind::NotFound {
+                    return Err(error);
+                }
+            }
+
+            let listener = UnixListener::bind(path)?;
+
+            Ok(Box::new(listener) as Box<dyn NodeListener>)
+        }
+        .boxed()
+    }
+
+    fn connect(&self, address: SocketAddr) -> BoxFuture<'static, io::Result<Box<dyn NodeStream>>> {
+        let path = self.path(address);
+
+        async move {
+            let socket = UnixStream::connect(path).await?;
+
+            Ok(Box::new(socket) as Box<dyn NodeStream>)
+        }
+        .boxed()
+    }
+}
+
+impl NodeListener for UnixListener {
+    fn accept(&mut self) -> BoxFuture<'_, io::Result<Box<dyn NodeStream>>> {
+        async move {
+            let (socket, _) = UnixListener::accept(self).await?;
+
+            Ok(Box::new(socket) as Box<dyn NodeStream>)
+        }
+        .boxed()
+    }
+}
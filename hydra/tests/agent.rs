@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use hydra::Agent;
+use hydra::AgentOperation;
+use hydra::GenServerOptions;
+use hydra::Supervisor;
+use hydra::SupervisorOptions;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum CounterOperation {
+    Increment,
+    Value(u64),
+}
+
+impl AgentOperation<u64> for CounterOperation {
+    fn apply(self, state: &mut u64) -> Self {
+        if let CounterOperation::Increment = self {
+            *state += 1;
+        }
+
+        CounterOperation::Value(*state)
+    }
+}
+
+#[hydra::test]
+async fn agent_works() {
+    let pid = Agent::<Vec<String>>::start_link(Vec::new, GenServerOptions::new())
+        .await
+        .unwrap();
+
+    Agent::<Vec<String>>::update(pid, |state| state.push(String::from("hello")), None)
+        .await
+        .unwrap();
+
+    Agent::<Vec<String>>::cast(pid, |state| state.push(String::from("world")));
+
+    let len = Agent::<Vec<String>>::get(pid, |state| state.len(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(len, 2);
+
+    let first = Agent::<Vec<String>>::get_and_update(pid, |state| state.remove(0), None)
+        .await
+        .unwrap();
+
+    assert_eq!(first, "hello");
+
+    let state = Agent::<Vec<String>>::get(pid, |state| state.clone(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(state, vec![String::from("world")]);
+}
+
+#[hydra::test]
+async fn agent_operation_works() {
+    Supervisor::with_children([Agent::<u64, CounterOperation>::child_spec(
+        || 0,
+        GenServerOptions::new().name("counter"),
+    )])
+    .start(SupervisorOptions::new())
+    .await
+    .unwrap();
+
+    Agent::<u64, CounterOperation>::cast_operation("counter", CounterOperation::Increment);
+
+    let reply = Agent::<u64, CounterOperation>::call_operation(
+        "counter",
+        CounterOperation::Increment,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(reply, CounterOperation::Value(2));
+
+    let value = Agent::<u64, CounterOperation>::get("counter", |state| *state, None)
+        .await
+        .unwrap();
+
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use hydra::Message;
+use hydra::Pid;
+use hydra::Process;
+
+#[hydra::test]
+async fn broadcast_shares_local_payload() {
+    let current = Process::current();
+    let payload = Arc::new(vec![0u8; 1024]);
+
+    let pids: Vec<Pid> = (0..3)
+        .map(|_| {
+            Process::spawn(async move {
+                let message: Message<Arc<Vec<u8>>> = Process::receive().await;
+
+                if let Message::User(payload) = message {
+                    Process::send(current, payload);
+                }
+            })
+        })
+        .collect();
+
+    Process::broadcast(pids.as_slice(), payload.clone());
+
+    for _ in 0..3 {
+        let message: Message<Arc<Vec<u8>>> = Process::receive().await;
+
+        let Message::User(received) = message else {
+            panic!("Expected payload!");
+        };
+
+        assert!(Arc::ptr_eq(&payload, &received));
+    }
+}
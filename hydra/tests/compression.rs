@@ -0,0 +1,160 @@
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+
+use tokio_util::codec::Decoder;
+use tokio_util::codec::Encoder;
+
+use hydra::frame::Codec;
+use hydra::frame::Frame;
+use hydra::frame::Send;
+
+use hydra::Compression;
+use hydra::MessageFormat;
+use hydra::NodeStats;
+
+fn send(message: Vec<u8>) -> Frame {
+    Send::with_pid(
+        NonZeroU64::new(1).unwrap(),
+        MessageFormat::MessagePack,
+        Arc::from(message),
+    )
+    .into()
+}
+
+/// Returns bytes that don't compress.
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+fn incompressible(size: usize) -> Vec<u8> {
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+
+    (0..size)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// Encodes the message with the given compression, returning the compression id from the marker, and the decoded message.
+fn round_trip(compression: Compression, threshold: usize, message: Vec<u8>) -> (u8, Vec<u8>) {
+    let mut codec = Codec::new(Arc::new(NodeStats::new()), 1024 * 1024);
+    let mut buffer = BytesMut::new();
+
+    codec.set_compression(compression, threshold);
+    codec.encode(send(message), &mut buffer).unwrap();
+
+    let marker = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+
+    let Some(Frame::Send(send)) = codec.decode(&mut buffer).unwrap() else {
+        panic!("Expected a send frame!");
+    };
+
+    assert!(buffer.is_empty());
+
+    ((marker >> 30) as u8, send.message.to_vec())
+}
+
+/// Encodes a large compressible message with the given compression, and decodes it with a smaller maximum message size.
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+fn decode_oversized(compression: Compression) -> bool {
+    let mut encoder = Codec::new(Arc::new(NodeStats::new()), 1024 * 1024);
+    let mut decoder = Codec::new(Arc::new(NodeStats::new()), 1024);
+    let mut buffer = BytesMut::new();
+
+    encoder.set_compression(compression, 0);
+    encoder
+        .encode(send(vec![1; 512 * 1024]), &mut buffer)
+        .unwrap();
+
+    decoder.decode(&mut buffer).is_err()
+}
+
+#[hydra::test]
+async fn compression_none_works() {
+    let (id, message) = round_trip(Compression::None, 0, vec![1; 4096]);
+
+    assert_eq!(id, 0);
+    assert_eq!(message, vec![1; 4096]);
+}
+
+#[hydra::test]
+async fn compression_rejects_unsupported_id() {
+    let mut codec = Codec::new(Arc::new(NodeStats::new()), 1024);
+    let mut buffer = BytesMut::new();
+
+    buffer.extend_from_slice(&(4u32 | (3 << 30)).to_le_bytes());
+    buffer.extend_from_slice(&[0; 4]);
+
+    assert!(codec.decode(&mut buffer).is_err());
+}
+
+#[cfg(feature = "lz4")]
+#[hydra::test]
+async fn compression_lz4_works() {
+    let (id, message) = round_trip(Compression::Lz4, 1024, vec![1; 4096]);
+
+    assert_eq!(id, 1);
+    assert_eq!(message, vec![1; 4096]);
+}
+
+#[cfg(feature = "lz4")]
+#[hydra::test]
+async fn compression_lz4_skips_small_frames() {
+    let (id, message) = round_trip(Compression::Lz4, 1024, vec![1; 512]);
+
+    assert_eq!(id, 0);
+    assert_eq!(message, vec![1; 512]);
+}
+
+#[cfg(feature = "lz4")]
+#[hydra::test]
+async fn compression_lz4_falls_back_to_raw() {
+    let data = incompressible(4096);
+    let (id, message) = round_trip(Compression::Lz4, 1024, data.clone());
+
+    assert_eq!(id, 0);
+    assert_eq!(message, data);
+}
+
+#[cfg(feature = "zstd")]
+#[hydra::test]
+async fn compression_zstd_works() {
+    let (id, message) = round_trip(Compression::Zstd(3), 1024, vec![1; 4096]);
+
+    assert_eq!(id, 2);
+    assert_eq!(message, vec![1; 4096]);
+}
+
+#[cfg(feature = "zstd")]
+#[hydra::test]
+async fn compression_zstd_skips_small_frames() {
+    let (id, message) = round_trip(Compression::Zstd(3), 1024, vec![1; 512]);
+
+    assert_eq!(id, 0);
+    assert_eq!(message, vec![1; 512]);
+}
+
+#[cfg(feature = "zstd")]
+#[hydra::test]
+async fn compression_zstd_falls_back_to_raw() {
+    let data = incompressible(4096);
+    let (id, message) = round_trip(Compression::Zstd(3), 1024, data.clone());
+
+    assert_eq!(id, 0);
+    assert_eq!(message, data);
+}
+
+#[cfg(feature = "lz4")]
+#[hydra::test]
+async fn compression_lz4_rejects_oversized_frames() {
+    assert!(decode_oversized(Compression::Lz4));
+}
+
+#[cfg(feature = "zstd")]
+#[hydra::test]
+async fn compression_zstd_rejects_oversized_frames() {
+    assert!(decode_oversized(Compression::Zstd(3)));
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use hydra::Agent;
+use hydra::AgentError;
+use hydra::CallError;
+use hydra::ChildSpec;
+use hydra::ExitReason;
+use hydra::GenServerOptions;
+use hydra::Message;
+use hydra::Process;
+use hydra::Restart;
+use hydra::Supervisor;
+use hydra::SupervisorOptions;
+use hydra::SystemMessage;
+
+#[hydra::test]
+async fn exit_reason_payload_works() {
+    let (pid, _) = Process::spawn_monitor(async {
+        Process::exit(
+            Process::current(),
+            ExitReason::with_payload("bad_request", &(404u16, String::from("not found"))),
+        );
+
+        let _ = Process::receive::<()>().await;
+    });
+
+    let message: Message<()> = Process::receive().await;
+
+    let Message::System(SystemMessage::ProcessDown(object, _, exit_reason)) = message else {
+        panic!("Expected process down message!");
+    };
+
+    assert!(object == pid);
+    assert!(exit_reason == "bad_request");
+    assert_eq!(
+        exit_reason.payload::<(u16, String)>(),
+        Some((404, String::from("not found")))
+    );
+}
+
+#[hydra::test]
+async fn exit_reason_panic_call_works() {
+    let pid = Agent::<u64>::start(|| 0, GenServerOptions::new())
+        .await
+        .unwrap();
+
+    let result = Agent::<u64>::get(pid, |_| panic!("boom"), None).await;
+
+    assert!(matches!(
+        result,
+        Err(AgentError::CallError(CallError::ServerDown(ExitReason::Panic(panic)))) if panic.message == "boom"
+    ));
+}
+
+#[hydra::test]
+async fn exit_reason_panic_restart_works() {
+    static STARTS: AtomicUsize = AtomicUsize::new(0);
+
+    let child = ChildSpec::new("child")
+        .restart(Restart::Transient)
+        .start(|| async {
+            Ok(Process::spawn_link(async {
+                if STARTS.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("boom");
+                }
+
+                Process::sleep(Duration::from_secs(60)).await;
+            }))
+        });
+
+    Supervisor::with_children([child])
+        .start(SupervisorOptions::new())
+        .await
+        .unwrap();
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(STARTS.load(Ordering::SeqCst), 2);
+}
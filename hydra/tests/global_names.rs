@@ -0,0 +1,153 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use hydra::Agent;
+use hydra::AgentError;
+use hydra::CallError;
+use hydra::Dest;
+use hydra::ExitReason;
+use hydra::GenServer;
+use hydra::GenServerOptions;
+use hydra::GlobalNames;
+use hydra::GlobalNamesError;
+use hydra::GlobalNamesMessage;
+use hydra::MemoryTransport;
+use hydra::Message;
+use hydra::Node;
+use hydra::NodeOptions;
+use hydra::Pid;
+use hydra::Process;
+use hydra::SystemMessage;
+use hydra::Task;
+
+/// Every server shares the same view of the global names, so tests that start one run one at a time.
+static GLOBAL_NAMES: Mutex<()> = Mutex::const_new(());
+
+#[hydra::test]
+async fn global_names_register_works() {
+    let _guard = GLOBAL_NAMES.lock().await;
+
+    GlobalNames::new().start().await.unwrap();
+
+    let current = Process::current();
+
+    let pid = Process::spawn(async move {
+        let message: Message<String> = Process::receive().await;
+
+        if let Message::User(message) = message {
+            Process::send(current, message);
+        }
+
+        let _ = Process::receive::<()>().await;
+    });
+
+    GlobalNames::register("leader", pid).await.unwrap();
+
+    assert_eq!(GlobalNames::whereis("leader"), Some(pid));
+    assert!(matches!(
+        GlobalNames::register("leader", current).await,
+        Err(GlobalNamesError::AlreadyRegistered(registered)) if registered == pid
+    ));
+
+    Process::send(Dest::Global("leader".into()), String::from("hello"));
+
+    let message: Message<String> = Process::receive().await;
+
+    assert!(matches!(message, Message::User(message) if message == "hello"));
+
+    let monitor = Process::monitor(Dest::Global("leader".into()));
+
+    Process::exit(pid, ExitReason::Kill);
+
+    let message: Message<()> = Process::receive().await;
+
+    assert!(matches!(
+        message,
+        Message::System(SystemMessage::ProcessDown(Dest::Global(name), reference, _)) if name == "leader" && reference == monitor
+    ));
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(GlobalNames::whereis("leader"), None);
+}
+
+#[hydra::test]
+async fn global_names_call_works() {
+    let _guard = GLOBAL_NAMES.lock().await;
+
+    GlobalNames::new().start().await.unwrap();
+
+    let pid = Agent::<u64>::start(|| 42, GenServerOptions::new())
+        .await
+        .unwrap();
+
+    GlobalNames::register("counter", pid).await.unwrap();
+
+    let counter = Dest::Global("counter".into());
+
+    assert!(!counter.is_local() && !counter.is_remote());
+    assert_eq!(
+        Agent::<u64>::get(counter, |state| *state, None)
+            .await
+            .unwrap(),
+        42
+    );
+
+    assert!(matches!(
+        Agent::<u64>::get(Dest::Global("missing".into()), |state| *state, None).await,
+        Err(AgentError::CallError(CallError::ServerDown(reason))) if reason == "noproc"
+    ));
+}
+
+#[hydra::test]
+async fn global_names_concurrent_register_works() {
+    let _guard = GLOBAL_NAMES.lock().await;
+
+    let local: SocketAddr = "127.0.0.1:1700".parse().unwrap();
+
+    // Servers are ranked by their node, so the node must be started.
+    Node::start(
+        "local",
+        NodeOptions::new()
+            .listen_address(local)
+            .broadcast_address(local)
+            .transport(MemoryTransport::new()),
+    );
+
+    let first = GenServer::start(GlobalNames::new(), GenServerOptions::new())
+        .await
+        .unwrap();
+    let second = GenServer::start(GlobalNames::new(), GenServerOptions::new())
+        .await
+        .unwrap();
+
+    GlobalNames::cast(first, GlobalNamesMessage::Discover(second));
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    // Register the same name on both servers at once, without retrying, so both lock it before seeing the other lock.
+    let register = |server: Pid| {
+        Task::async_nolink(async move {
+            let pid = Process::spawn(Process::sleep(Duration::from_secs(60)));
+            let message = GlobalNamesMessage::Register(String::from("race"), pid);
+
+            match GlobalNames::call(server, message, None).await {
+                Ok(GlobalNamesMessage::RegisterSuccess) => Some(pid),
+                _ => None,
+            }
+        })
+    };
+
+    let first = register(first);
+    let second = register(second);
+
+    let first = Task::await_async(first, None).await.unwrap();
+    let second = Task::await_async(second, None).await.unwrap();
+
+    assert!(first.is_some() != second.is_some());
+    assert_eq!(GlobalNames::whereis("race"), first.or(second));
+
+    Node::stop();
+}
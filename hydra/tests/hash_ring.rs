@@ -1,3 +1,6 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::BuildHasherDefault;
+
 use hydra::HashRing;
 
 #[test]
@@ -64,3 +67,18 @@ fn hash_ring_overrides() {
 
     assert_eq!(node3.unwrap(), 0xC0DEBEEF);
 }
+
+#[test]
+fn hash_ring_with_hasher_is_deterministic() {
+    type Hasher = BuildHasherDefault<DefaultHasher>;
+
+    let ring1: HashRing<u64, Hasher> = HashRing::with_hasher(Hasher::default());
+    let ring2: HashRing<u64, Hasher> = HashRing::with_hasher(Hasher::default());
+
+    ring1.add_nodes([(0, 0xDEADBEEF), (1, 0xDEADC0DE)]);
+    ring2.add_nodes([(1, 0xDEADC0DE), (0, 0xDEADBEEF)]);
+
+    for key in 0..100 {
+        assert_eq!(ring1.find_node(key), ring2.find_node(key));
+    }
+}
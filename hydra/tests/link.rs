@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use hydra::ExitReason;
 use hydra::Message;
 use hydra::Process;
 use hydra::ProcessFlags;
@@ -31,7 +32,13 @@ async fn link_trap_exit_works() {
 
     if let Message::System(SystemMessage::Exit(from, exit_reason)) = message {
         assert!(from == pid);
-        assert!(exit_reason == "we died!");
+
+        let ExitReason::Panic(panic) = exit_reason else {
+            panic!("Expected panic exit reason!");
+        };
+
+        assert_eq!(panic.message, "we died!");
+        assert_eq!(panic.file.as_deref(), Some(file!()));
     } else {
         panic!("Expected exit signal!");
     }
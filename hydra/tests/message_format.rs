@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use hydra::Message;
+use hydra::MessageFormat;
+use hydra::Pid;
+use hydra::Process;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[hydra::test]
+async fn message_format_register_works() {
+    MessageFormat::register::<Point>(MessageFormat::Bincode);
+
+    let current = Process::current();
+
+    let pids: Vec<Pid> = (0..2)
+        .map(|_| {
+            Process::spawn(async move {
+                let message: Message<Point> = Process::receive().await;
+
+                if let Message::User(point) = message {
+                    Process::send(current, point);
+                }
+            })
+        })
+        .collect();
+
+    Process::send(pids.as_slice(), Point { x: 1, y: 2 });
+
+    for _ in 0..2 {
+        let message: Message<Point> = Process::receive().await;
+
+        assert!(matches!(message, Message::User(Point { x: 1, y: 2 })));
+    }
+}
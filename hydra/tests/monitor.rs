@@ -22,7 +22,7 @@ async fn monitor_works() {
     if let Message::System(SystemMessage::ProcessDown(object, mref, exit_reason)) = message {
         assert!(object == pid);
         assert!(reference == mref);
-        assert!(matches!(exit_reason, ExitReason::Custom(_)));
+        assert!(matches!(exit_reason, ExitReason::Panic(_)));
     } else {
         panic!("Expected process down message!");
     }
@@ -44,7 +44,7 @@ async fn monitor_named_works() {
     if let Message::System(SystemMessage::ProcessDown(object, mref, exit_reason)) = message {
         assert!(object == "monitor_me");
         assert!(reference == mref);
-        assert!(matches!(exit_reason, ExitReason::Custom(_)));
+        assert!(matches!(exit_reason, ExitReason::Panic(_)));
     } else {
         panic!("Expected process down message!");
     }
@@ -116,7 +116,7 @@ async fn spawn_monitor_works() {
     if let Message::System(SystemMessage::ProcessDown(object, mref, exit_reason)) = message {
         assert!(object == pid);
         assert!(reference == mref);
-        assert!(matches!(exit_reason, ExitReason::Custom(_)));
+        assert!(matches!(exit_reason, ExitReason::Panic(_)));
     } else {
         panic!("Expected process down message!");
     }
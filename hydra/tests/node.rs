@@ -0,0 +1,616 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::SinkExt;
+use futures_util::StreamExt;
+
+use tokio::io::AsyncWriteExt;
+
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+
+use tokio_util::codec::Framed;
+
+use hydra::frame::Codec;
+use hydra::frame::Frame;
+use hydra::frame::Hello;
+use hydra::frame::Monitor;
+use hydra::frame::Peers;
+use hydra::frame::Ping;
+use hydra::frame::Pong;
+use hydra::frame::PROTOCOL_VERSION;
+
+use hydra::ExitReason;
+use hydra::MemoryTransport;
+use hydra::Message;
+use hydra::Node;
+use hydra::NodeOptions;
+use hydra::NodeState;
+use hydra::NodeStats;
+use hydra::NodeStream;
+use hydra::NodeTransport;
+use hydra::Process;
+use hydra::ReconnectPolicy;
+use hydra::SystemMessage;
+
+/// Only one local node can run at a time, so tests that start it run one at a time.
+///
+/// Each test listens on its own address, since a stopped node is only dropped along with the runtime of its test.
+static NODE: Mutex<()> = Mutex::const_new(());
+
+/// Starts the local node over the memory transport, it must be stopped before the returned guard is dropped.
+async fn start_node(local: SocketAddr, options: NodeOptions) -> MutexGuard<'static, ()> {
+    let guard = NODE.lock().await;
+
+    Node::start(
+        "local",
+        options
+            .listen_address(local)
+            .broadcast_address(local)
+            .transport(MemoryTransport::new()),
+    );
+
+    guard
+}
+
+/// Connects to the local node as a remote node would, once it's listening.
+async fn connect_node(local: SocketAddr) -> Framed<Box<dyn NodeStream>, Codec> {
+    loop {
+        if let Ok(socket) = MemoryTransport::new().connect(local).await {
+            return Framed::new(socket, Codec::new(Arc::new(NodeStats::new()), 1024));
+        }
+
+        Process::sleep(Duration::from_millis(1)).await;
+    }
+}
+
+/// Connects to the local node, and completes the handshake as the given remote node.
+async fn handshake_node(
+    local: SocketAddr,
+    name: &str,
+    address: SocketAddr,
+    hidden: bool,
+) -> Framed<Box<dyn NodeStream>, Codec> {
+    let mut connection = connect_node(local).await;
+
+    connection
+        .send(Hello::new(String::from(name), address, hidden).into())
+        .await
+        .unwrap();
+
+    let Some(Ok(Frame::Hello(_))) = connection.next().await else {
+        panic!("Expected a hello frame!");
+    };
+
+    connection
+}
+
+/// Waits for the given node to come up, the current process must monitor all nodes.
+async fn node_up(node: (&str, SocketAddr)) {
+    loop {
+        if let Message::System(SystemMessage::NodeUp(up, _)) = Process::receive::<()>().await {
+            if up == node {
+                return;
+            }
+        }
+    }
+}
+
+/// Waits for the given node to go down, the current process must monitor all nodes.
+async fn node_down(node: (&str, SocketAddr)) -> ExitReason {
+    loop {
+        if let Message::System(SystemMessage::NodeDown(down, _, exit_reason)) =
+            Process::receive::<()>().await
+        {
+            if down == node {
+                return exit_reason;
+            }
+        }
+    }
+}
+
+#[hydra::test]
+async fn node_pending_messages_are_bounded() {
+    let local: SocketAddr = "127.0.0.1:1500".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new().max_pending_messages(10)).await;
+
+    let remote: SocketAddr = "127.0.0.1:1401".parse().unwrap();
+
+    // A listener that never accepts, so the node stays pending until the handshake times out.
+    let _listener = MemoryTransport::new().listen(remote).await.unwrap();
+
+    Node::connect_with_policy(
+        ("remote", remote),
+        ReconnectPolicy::Interval(Duration::from_secs(1)),
+    );
+
+    for i in 0..15 {
+        Process::send(("test", ("remote", remote)), i);
+    }
+
+    let info = Node::info(("remote", remote)).unwrap();
+
+    assert_eq!(info.state, NodeState::Pending);
+    assert_eq!(info.dropped_messages, 5);
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_handshake_works() {
+    let local: SocketAddr = "127.0.0.1:1501".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1402".parse().unwrap();
+
+    let mut connection = connect_node(local).await;
+
+    connection
+        .send(Hello::new(String::from("peer"), peer, false).into())
+        .await
+        .unwrap();
+
+    let Some(Ok(Frame::Hello(mut hello))) = connection.next().await else {
+        panic!("Expected a hello frame!");
+    };
+
+    assert_eq!(hello.name, "local");
+    assert!(hello.validate());
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(
+        Node::info(("peer", peer)).unwrap().state,
+        NodeState::Connected
+    );
+
+    drop(connection);
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(Node::info(("peer", peer)).unwrap().state, NodeState::Known);
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_handshake_rejects_other_protocol_versions() {
+    let local: SocketAddr = "127.0.0.1:1502".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1403".parse().unwrap();
+
+    let mut connection = connect_node(local).await;
+
+    let mut hello = Hello::new(String::from("peer"), peer, false);
+
+    hello.version = PROTOCOL_VERSION + 1;
+
+    connection.send(hello.into()).await.unwrap();
+
+    assert!(matches!(connection.next().await, Some(Ok(Frame::Hello(_)))));
+    assert!(connection.next().await.is_none());
+
+    assert!(Node::info(("peer", peer)).is_none());
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_full_mesh_shares_peers() {
+    let local: SocketAddr = "127.0.0.1:1503".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new().full_mesh(true)).await;
+
+    let first: SocketAddr = "127.0.0.1:1404".parse().unwrap();
+    let hidden: SocketAddr = "127.0.0.1:1405".parse().unwrap();
+    let second: SocketAddr = "127.0.0.1:1406".parse().unwrap();
+
+    let mut first_connection = handshake_node(local, "first", first, false).await;
+
+    let Some(Ok(Frame::Peers(peers))) = first_connection.next().await else {
+        panic!("Expected a peers frame!");
+    };
+
+    assert!(peers.nodes.is_empty());
+
+    let _hidden_connection = handshake_node(local, "hidden", hidden, true).await;
+    let mut second_connection = handshake_node(local, "second", second, false).await;
+
+    // Hidden nodes are never shared with, or shared to, other nodes.
+    let Some(Ok(Frame::Peers(peers))) = second_connection.next().await else {
+        panic!("Expected a peers frame!");
+    };
+
+    assert_eq!(peers.nodes, vec![(String::from("first"), first)]);
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_full_mesh_connects_to_peers() {
+    let local: SocketAddr = "127.0.0.1:1504".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new().full_mesh(true)).await;
+
+    let peer: SocketAddr = "127.0.0.1:1407".parse().unwrap();
+    let other: SocketAddr = "127.0.0.1:1408".parse().unwrap();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    let Some(Ok(Frame::Peers(_))) = connection.next().await else {
+        panic!("Expected a peers frame!");
+    };
+
+    let peers = Peers::new(vec![
+        (String::from("other"), other),
+        (String::from("local"), local),
+    ]);
+
+    connection.send(peers.into()).await.unwrap();
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    // The local node skips itself, and attempts to connect to the other node.
+    assert!(Node::info(("other", other)).is_some());
+    assert!(Node::info(("local", local)).is_none());
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_ignores_peers_without_full_mesh() {
+    let local: SocketAddr = "127.0.0.1:1505".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1409".parse().unwrap();
+    let other: SocketAddr = "127.0.0.1:1410".parse().unwrap();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    connection
+        .send(Peers::new(vec![(String::from("other"), other)]).into())
+        .await
+        .unwrap();
+
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert!(Node::info(("other", other)).is_none());
+    assert_eq!(
+        Node::info(("peer", peer)).unwrap().state,
+        NodeState::Connected
+    );
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_heartbeats_measure_round_trip_time() {
+    let local: SocketAddr = "127.0.0.1:1506".parse().unwrap();
+
+    let _node = start_node(
+        local,
+        NodeOptions::new().heartbeat_interval(Duration::from_millis(20)),
+    )
+    .await;
+
+    let peer: SocketAddr = "127.0.0.1:1411".parse().unwrap();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    connection.send(Ping::new(42).into()).await.unwrap();
+
+    let mut pong = None;
+
+    while pong.is_none() {
+        match connection.next().await {
+            Some(Ok(Frame::Ping(ping))) => {
+                connection
+                    .send(Pong::new(ping.timestamp).into())
+                    .await
+                    .unwrap();
+            }
+            Some(Ok(Frame::Pong(frame))) => pong = Some(frame.timestamp),
+            frame => panic!("Unexpected frame: {:?}", frame),
+        }
+    }
+
+    assert_eq!(pong, Some(42));
+
+    // Answer the next heartbeat, so that the round trip time is measured.
+    let Some(Ok(Frame::Ping(ping))) = connection.next().await else {
+        panic!("Expected a ping frame!");
+    };
+
+    connection
+        .send(Pong::new(ping.timestamp).into())
+        .await
+        .unwrap();
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    let info = Node::info(("peer", peer)).unwrap();
+
+    assert!(info.round_trip_time.is_some());
+    assert!(info.last_seen.is_some());
+    assert!(info.frames_in >= 2);
+    assert!(info.frames_out >= 2);
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_monitor_all_works() {
+    let local: SocketAddr = "127.0.0.1:1507".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1412".parse().unwrap();
+
+    Node::monitor_all();
+
+    let connection = handshake_node(local, "peer", peer, false).await;
+
+    let Message::System(SystemMessage::NodeUp(up, _)) = Process::receive::<()>().await else {
+        panic!("Expected a node up message!");
+    };
+
+    assert_eq!(up, ("peer", peer));
+
+    drop(connection);
+
+    assert_eq!(node_down(("peer", peer)).await, "connection_closed");
+
+    let _connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    Node::disconnect(("peer", peer));
+
+    assert_eq!(node_down(("peer", peer)).await, "disconnect");
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_heartbeat_timeout_works() {
+    let local: SocketAddr = "127.0.0.1:1508".parse().unwrap();
+
+    let _node = start_node(
+        local,
+        NodeOptions::new().heartbeat_timeout(Duration::from_millis(50)),
+    )
+    .await;
+
+    let peer: SocketAddr = "127.0.0.1:1413".parse().unwrap();
+
+    Node::monitor_all();
+
+    let _connection = handshake_node(local, "peer", peer, false).await;
+
+    assert_eq!(node_down(("peer", peer)).await, "heartbeat_timeout");
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_disconnects_on_invalid_frames() {
+    let local: SocketAddr = "127.0.0.1:1509".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1414".parse().unwrap();
+
+    Node::monitor_all();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    // A raw frame of four bytes, which isn't a valid frame kind.
+    let mut garbage = 4u32.to_le_bytes().to_vec();
+
+    garbage.extend_from_slice(&[0xFF; 4]);
+
+    connection.get_mut().write_all(&garbage).await.unwrap();
+
+    assert_eq!(node_down(("peer", peer)).await, "decode_error");
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_disconnects_on_malformed_monitors() {
+    let local: SocketAddr = "127.0.0.1:1510".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1415".parse().unwrap();
+
+    Node::monitor_all();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    // Installing a monitor requires the process that is monitoring.
+    connection
+        .send(Monitor::new(true, Some(1), None, None, 1).into())
+        .await
+        .unwrap();
+
+    assert_eq!(node_down(("peer", peer)).await, "decode_error");
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_disconnects_on_repeated_handshakes() {
+    let local: SocketAddr = "127.0.0.1:1511".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1416".parse().unwrap();
+
+    Node::monitor_all();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    connection
+        .send(Hello::new(String::from("peer"), peer, false).into())
+        .await
+        .unwrap();
+
+    assert_eq!(node_down(("peer", peer)).await, "handshake_rejected");
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_heartbeats_skip_ahead_of_a_backlog() {
+    let local: SocketAddr = "127.0.0.1:1512".parse().unwrap();
+
+    let _node = start_node(
+        local,
+        NodeOptions::new().heartbeat_interval(Duration::from_millis(20)),
+    )
+    .await;
+
+    let peer: SocketAddr = "127.0.0.1:1417".parse().unwrap();
+
+    Node::monitor_all();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    // Queue far more than the transport buffers, so that the lanes stay saturated while we read slowly.
+    for _ in 0..4000 {
+        Process::send(("test", ("peer", peer)), vec![0u8; 512]);
+    }
+
+    let mut sends = 0;
+    let mut sends_after_ping = None;
+
+    while sends < 4000 {
+        match connection.next().await {
+            Some(Ok(Frame::Send(_))) => {
+                sends += 1;
+
+                if let Some(sends_after_ping) = &mut sends_after_ping {
+                    *sends_after_ping += 1;
+                }
+
+                if sends % 50 == 0 {
+                    Process::sleep(Duration::from_millis(1)).await;
+                }
+            }
+            Some(Ok(Frame::Ping(_))) => {
+                sends_after_ping.get_or_insert(0);
+            }
+            frame => panic!("Unexpected frame: {:?}", frame),
+        }
+    }
+
+    assert!(sends_after_ping.is_some_and(|sends_after_ping| sends_after_ping > 0));
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_pongs_skip_ahead_of_a_backlog() {
+    let local: SocketAddr = "127.0.0.1:1513".parse().unwrap();
+
+    let _node = start_node(local, NodeOptions::new()).await;
+
+    let peer: SocketAddr = "127.0.0.1:1418".parse().unwrap();
+
+    Node::monitor_all();
+
+    let mut connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    // Queue far more than the transport buffers, so that the lanes stay saturated while we read slowly.
+    for _ in 0..4000 {
+        Process::send(("test", ("peer", peer)), vec![0u8; 512]);
+    }
+
+    connection.send(Ping::new(42).into()).await.unwrap();
+
+    let mut sends = 0;
+    let mut sends_after_pong = None;
+
+    while sends < 4000 {
+        match connection.next().await {
+            Some(Ok(Frame::Send(_))) => {
+                sends += 1;
+
+                if let Some(sends_after_pong) = &mut sends_after_pong {
+                    *sends_after_pong += 1;
+                }
+
+                if sends % 50 == 0 {
+                    Process::sleep(Duration::from_millis(1)).await;
+                }
+            }
+            Some(Ok(Frame::Pong(pong))) => {
+                assert_eq!(pong.timestamp, 42);
+
+                sends_after_pong.get_or_insert(0);
+            }
+            frame => panic!("Unexpected frame: {:?}", frame),
+        }
+    }
+
+    assert!(sends_after_pong.is_some_and(|sends_after_pong| sends_after_pong > 0));
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn node_reconnects_after_connection_drop() {
+    let local: SocketAddr = "127.0.0.1:1514".parse().unwrap();
+
+    let _node = start_node(
+        local,
+        NodeOptions::new().reconnect_policy(ReconnectPolicy::Interval(Duration::from_millis(10))),
+    )
+    .await;
+
+    let peer: SocketAddr = "127.0.0.1:1419".parse().unwrap();
+
+    let mut listener = MemoryTransport::new().listen(peer).await.unwrap();
+
+    Node::monitor_all();
+
+    let connection = handshake_node(local, "peer", peer, false).await;
+
+    node_up(("peer", peer)).await;
+
+    drop(connection);
+
+    assert_eq!(node_down(("peer", peer)).await, "connection_closed");
+    assert_eq!(
+        Node::info(("peer", peer)).unwrap().state,
+        NodeState::Pending
+    );
+
+    let socket = listener.accept().await.unwrap();
+    let mut connection = Framed::new(socket, Codec::new(Arc::new(NodeStats::new()), 1024));
+
+    let Some(Ok(Frame::Hello(hello))) = connection.next().await else {
+        panic!("Expected a hello frame!");
+    };
+
+    assert_eq!(hello.name, "local");
+
+    Node::stop();
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use hydra::frame::Fragment;
+use hydra::frame::Frame;
+use hydra::frame::Send;
+use hydra::frame::MAX_FRAGMENTED_MESSAGES;
+
+use hydra::MessageFormat;
+use hydra::NodeFragments;
+use hydra::NodeLanes;
+use hydra::NodeOptions;
+
+fn send(id: u64, size: usize) -> Send {
+    Send::with_pid(
+        NonZeroU64::new(id).unwrap(),
+        MessageFormat::MessagePack,
+        Arc::from(vec![id as u8; size]),
+    )
+}
+
+#[hydra::test]
+async fn fragment_split_works() {
+    let fragments = Fragment::split(7, send(1, 100), 16).unwrap();
+
+    assert!(fragments.len() > 1);
+    assert!(fragments.iter().all(|fragment| fragment.id == 7));
+    assert!(fragments.iter().all(|fragment| fragment.data.len() <= 16));
+    assert!(fragments.back().unwrap().last);
+    assert!(fragments
+        .iter()
+        .rev()
+        .skip(1)
+        .all(|fragment| !fragment.last));
+
+    let data: Vec<u8> = fragments
+        .into_iter()
+        .flat_map(|fragment| fragment.data)
+        .collect();
+
+    let joined = Fragment::join(&data).unwrap();
+
+    assert_eq!(&*joined.message, &[1u8; 100]);
+}
+
+#[hydra::test]
+async fn node_fragments_reassembles_interleaved_messages() {
+    let mut first = Fragment::split(0, send(1, 100), 16).unwrap();
+    let mut second = Fragment::split(1, send(2, 50), 16).unwrap();
+
+    let mut fragments = NodeFragments::new(1024);
+    let mut messages = Vec::new();
+
+    while !first.is_empty() || !second.is_empty() {
+        for fragment in [first.pop_front(), second.pop_front()]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(send) = fragments.push(fragment).unwrap() {
+                messages.push(send.message.to_vec());
+            }
+        }
+    }
+
+    assert_eq!(messages, vec![vec![2u8; 50], vec![1u8; 100]]);
+    assert!(fragments.is_empty());
+}
+
+#[hydra::test]
+async fn node_fragments_rejects_oversized_messages() {
+    let mut fragments = NodeFragments::new(64);
+
+    let result = Fragment::split(0, send(1, 100), 16)
+        .unwrap()
+        .into_iter()
+        .map(|fragment| fragments.push(fragment))
+        .find(Result::is_err);
+
+    assert_eq!(result.unwrap().unwrap_err(), "message_too_large");
+}
+
+#[hydra::test]
+async fn node_fragments_rejects_too_many_messages() {
+    let mut fragments = NodeFragments::new(1024);
+
+    for id in 0..MAX_FRAGMENTED_MESSAGES as u64 {
+        let result = fragments.push(Fragment::new(id, false, vec![0; 8]));
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    assert_eq!(fragments.len(), MAX_FRAGMENTED_MESSAGES);
+
+    let result = fragments.push(Fragment::new(
+        MAX_FRAGMENTED_MESSAGES as u64,
+        false,
+        vec![0; 8],
+    ));
+
+    assert_eq!(result.unwrap_err(), "too_many_fragments");
+}
+
+#[hydra::test]
+async fn node_lanes_interleaves_fragments() {
+    let mut lanes = NodeLanes::new(&NodeOptions::new().fragment_size(16));
+
+    lanes.push(Some(1), send(1, 100).into());
+    lanes.push(Some(2), send(2, 8).into());
+
+    assert!(matches!(lanes.pop(), Some(Frame::Fragment(_))));
+    assert!(matches!(lanes.pop(), Some(Frame::Send(send)) if send.message.len() == 8));
+
+    while let Some(frame) = lanes.pop() {
+        assert!(matches!(frame, Frame::Fragment(_)));
+    }
+
+    assert!(lanes.is_empty());
+}
+
+#[hydra::test]
+async fn node_lanes_bounds_fragmented_messages() {
+    let mut lanes = NodeLanes::new(&NodeOptions::new().fragment_size(16));
+
+    for origin in 1..=(MAX_FRAGMENTED_MESSAGES as u64 * 2) {
+        lanes.push(Some(origin), send(origin, 100).into());
+    }
+
+    let mut open = BTreeSet::new();
+    let mut fragments = NodeFragments::new(1024);
+    let mut messages = 0;
+
+    while let Some(frame) = lanes.pop() {
+        let Frame::Fragment(fragment) = frame else {
+            panic!("Expected a fragment!");
+        };
+
+        if fragment.last {
+            open.remove(&fragment.id);
+        } else {
+            open.insert(fragment.id);
+        }
+
+        assert!(open.len() <= MAX_FRAGMENTED_MESSAGES);
+
+        if fragments.push(fragment).unwrap().is_some() {
+            messages += 1;
+        }
+    }
+
+    assert_eq!(messages, MAX_FRAGMENTED_MESSAGES * 2);
+}
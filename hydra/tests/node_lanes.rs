@@ -0,0 +1,87 @@
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use hydra::frame::Frame;
+use hydra::frame::Link;
+use hydra::frame::Ping;
+use hydra::frame::Send;
+use hydra::frame::SendTarget;
+
+use hydra::MessageFormat;
+use hydra::NodeLanes;
+use hydra::NodeOptions;
+
+fn send(id: u64) -> Frame {
+    Send::with_pid(
+        NonZeroU64::new(id).unwrap(),
+        MessageFormat::MessagePack,
+        Arc::from(vec![0u8; 8]),
+    )
+    .into()
+}
+
+/// Pops every frame, describing each one by its kind and the process id it carries.
+fn drain(lanes: &mut NodeLanes) -> Vec<(&'static str, u64)> {
+    let mut frames = Vec::new();
+
+    while let Some(frame) = lanes.pop() {
+        frames.push(match frame {
+            Frame::Send(send) => match &send.targets[..] {
+                [SendTarget::Pid(id)] => ("send", id.get()),
+                targets => panic!("Unexpected targets: {:?}", targets),
+            },
+            Frame::Link(link) => ("link", link.from_id),
+            Frame::Ping(ping) => ("ping", ping.timestamp),
+            frame => panic!("Unexpected frame: {:?}", frame),
+        });
+    }
+
+    assert!(lanes.is_empty());
+
+    frames
+}
+
+#[hydra::test]
+async fn node_lanes_prioritizes_control_frames() {
+    let mut lanes = NodeLanes::new(&NodeOptions::new());
+
+    lanes.push(Some(1), send(1));
+    lanes.push(Some(1), send(1));
+    lanes.push(Some(2), Link::new(true, 10, 2).into());
+    lanes.push(None, Ping::new(3).into());
+
+    assert_eq!(
+        drain(&mut lanes),
+        [("link", 2), ("ping", 3), ("send", 1), ("send", 1)]
+    );
+}
+
+#[hydra::test]
+async fn node_lanes_keeps_order_per_origin() {
+    let mut lanes = NodeLanes::new(&NodeOptions::new());
+
+    lanes.push(Some(1), send(1));
+    lanes.push(Some(1), Link::new(true, 10, 1).into());
+    lanes.push(Some(2), Link::new(true, 10, 2).into());
+
+    // The link from origin 1 waits for its send, the link from origin 2 skips ahead.
+    assert_eq!(drain(&mut lanes), [("link", 2), ("send", 1), ("link", 1)]);
+
+    // Once origin 1 has nothing queued, its control frames skip ahead again.
+    lanes.push(Some(2), send(2));
+    lanes.push(Some(1), Link::new(false, 10, 1).into());
+
+    assert_eq!(drain(&mut lanes), [("link", 1), ("send", 2)]);
+}
+
+#[hydra::test]
+async fn node_lanes_disabled_keeps_queue_order() {
+    let mut lanes = NodeLanes::new(&NodeOptions::new().priority_lanes(false));
+
+    lanes.push(Some(1), send(1));
+    lanes.push(Some(2), Link::new(true, 10, 2).into());
+    lanes.push(None, Ping::new(3).into());
+
+    // Heartbeats are always sent first, so that a busy connection isn't timed out.
+    assert_eq!(drain(&mut lanes), [("ping", 3), ("send", 1), ("link", 2)]);
+}
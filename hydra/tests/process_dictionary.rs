@@ -0,0 +1,42 @@
+use hydra::Message;
+use hydra::Process;
+
+#[derive(Debug, Clone, PartialEq)]
+struct TraceId(u64);
+
+#[derive(Debug, Clone, PartialEq)]
+struct TenantId(u64);
+
+#[hydra::test]
+async fn process_dictionary_works() {
+    assert_eq!(Process::put(TenantId(1)), None);
+    assert_eq!(Process::put(TenantId(2)), Some(TenantId(1)));
+    assert_eq!(Process::put_inherited(TraceId(3)), None);
+
+    assert_eq!(Process::get::<TenantId>(), Some(TenantId(2)));
+    assert_eq!(Process::get::<TraceId>(), Some(TraceId(3)));
+
+    let current = Process::current();
+
+    Process::spawn(async move {
+        Process::send(
+            current,
+            (
+                Process::get::<TenantId>().map(|tenant| tenant.0),
+                Process::get::<TraceId>().map(|trace| trace.0),
+            ),
+        );
+    });
+
+    let message: Message<(Option<u64>, Option<u64>)> = Process::receive().await;
+
+    let Message::User((tenant, trace)) = message else {
+        panic!("Expected user message!");
+    };
+
+    assert_eq!(tenant, None);
+    assert_eq!(trace, Some(3));
+
+    assert_eq!(Process::delete::<TenantId>(), Some(TenantId(2)));
+    assert_eq!(Process::get::<TenantId>(), None);
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use hydra::ExitReason;
+use hydra::Message;
+use hydra::Process;
+use hydra::ProcessGroups;
+
+#[hydra::test]
+async fn process_groups_join_leave_works() {
+    ProcessGroups::new("groups").start().await.unwrap();
+
+    let current = Process::current();
+
+    let pid1 = Process::spawn(async move {
+        let message: Message<String> = Process::receive().await;
+
+        if let Message::User(message) = message {
+            Process::send(current, message);
+        }
+
+        Process::sleep(Duration::from_secs(60)).await;
+    });
+
+    let pid2 = Process::spawn(async {
+        let _ = Process::receive::<()>().await;
+    });
+
+    ProcessGroups::join("groups", "workers", pid1)
+        .await
+        .unwrap();
+    ProcessGroups::join("groups", "workers", pid2)
+        .await
+        .unwrap();
+
+    assert_eq!(ProcessGroups::get_members("groups", "workers").len(), 2);
+    assert_eq!(
+        ProcessGroups::get_local_members("groups", "workers").len(),
+        2
+    );
+    assert_eq!(
+        ProcessGroups::which_groups("groups"),
+        vec![String::from("workers")]
+    );
+
+    ProcessGroups::leave("groups", "workers", pid2)
+        .await
+        .unwrap();
+
+    assert_eq!(ProcessGroups::get_members("groups", "workers"), vec![pid1]);
+
+    Process::send(
+        ProcessGroups::get_members("groups", "workers"),
+        String::from("hello"),
+    );
+
+    let message: Message<String> = Process::receive().await;
+
+    assert!(matches!(message, Message::User(message) if message == "hello"));
+
+    assert!(Process::alive(pid1));
+
+    Process::exit(pid1, ExitReason::Kill);
+    Process::sleep(Duration::from_millis(50)).await;
+
+    assert!(!ProcessGroups::get_members("groups", "workers").contains(&pid1));
+    assert!(ProcessGroups::get_members("groups", "workers").is_empty());
+    assert!(ProcessGroups::which_groups("groups").is_empty());
+}
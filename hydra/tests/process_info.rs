@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use hydra::ChildSpec;
+use hydra::Process;
+use hydra::SpawnOptions;
+use hydra::Supervisor;
+use hydra::SupervisorOptions;
+
+#[hydra::test]
+async fn process_label_and_metadata_works() {
+    Process::set_label("test");
+    Process::set_metadata("tenant", "acme");
+
+    let info = Process::info(Process::current()).unwrap();
+
+    assert_eq!(info.label.as_deref(), Some("test"));
+    assert_eq!(info.metadata["tenant"], "acme");
+
+    Process::remove_metadata("tenant");
+
+    assert!(Process::info(Process::current())
+        .unwrap()
+        .metadata
+        .is_empty());
+
+    let supervisor = Supervisor::with_children([ChildSpec::new("worker").start(|| async {
+        let (pid, _) = Process::spawn_opt(
+            Process::sleep(Duration::from_secs(60)),
+            SpawnOptions::new().name("worker"),
+        )
+        .unwrap();
+
+        Ok(pid)
+    })])
+    .start(SupervisorOptions::new())
+    .await
+    .unwrap();
+
+    let info = Process::info(supervisor).unwrap();
+
+    assert_eq!(info.label.as_deref(), Some("hydra::supervisor::Supervisor"));
+
+    let info = Process::info(Process::whereis("worker").unwrap()).unwrap();
+
+    assert_eq!(info.label.as_deref(), Some("worker"));
+    assert_eq!(info.metadata["child_id"], "worker");
+}
+
+#[hydra::test]
+async fn process_stats_works() {
+    let current = Process::current();
+
+    let pid = Process::spawn(async move {
+        // Block the poll on purpose so this process is the busiest.
+        std::thread::sleep(Duration::from_millis(20));
+
+        Process::send(current, ());
+
+        let _ = Process::receive::<()>().await;
+
+        Process::sleep(Duration::from_secs(60)).await;
+    });
+
+    let _ = Process::receive::<()>().await;
+
+    Process::send(pid, ());
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    let top = Process::top(usize::MAX);
+
+    assert!(top
+        .windows(2)
+        .all(|top| top[0].1.busy_time >= top[1].1.busy_time));
+
+    let (_, info) = top.into_iter().find(|(top, _)| *top == pid).unwrap();
+
+    assert!(info.polls >= 2);
+    assert!(info.busy_time >= Duration::from_millis(20));
+    assert!(info.last_active.is_some());
+    assert_eq!(info.messages_sent, 1);
+    assert_eq!(info.messages_received, 1);
+    assert!(info.estimated_size > 0);
+}
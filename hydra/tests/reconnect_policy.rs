@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use hydra::ReconnectPolicy;
+
+#[hydra::test]
+async fn reconnect_policy_never_works() {
+    assert_eq!(ReconnectPolicy::Never.delay(1), None);
+    assert_eq!(ReconnectPolicy::Never.delay(10), None);
+}
+
+#[hydra::test]
+async fn reconnect_policy_interval_works() {
+    let policy = ReconnectPolicy::Interval(Duration::from_millis(250));
+
+    assert_eq!(policy.delay(1), Some(Duration::from_millis(250)));
+    assert_eq!(policy.delay(10), Some(Duration::from_millis(250)));
+}
+
+#[hydra::test]
+async fn reconnect_policy_backoff_works() {
+    let policy = ReconnectPolicy::Backoff {
+        initial: Duration::from_millis(100),
+        max: Duration::from_millis(1000),
+    };
+
+    let delays: Vec<_> = (1..=6).map(|attempt| policy.delay(attempt)).collect();
+
+    assert_eq!(
+        delays,
+        [100, 200, 400, 800, 1000, 1000].map(|delay| Some(Duration::from_millis(delay)))
+    );
+
+    assert_eq!(policy.delay(u32::MAX), Some(Duration::from_millis(1000)));
+}
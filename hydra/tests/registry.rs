@@ -0,0 +1,229 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use hydra::MemoryTransport;
+use hydra::Message;
+use hydra::Node;
+use hydra::NodeOptions;
+use hydra::Process;
+use hydra::Registry;
+use hydra::RegistryEvent;
+use hydra::RegistryKey;
+use hydra::RegistryOptions;
+
+#[hydra::test]
+async fn registry_duplicate_keys_dispatch_works() {
+    Registry::new("pubsub")
+        .with_duplicate_keys()
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    let current = Process::current();
+
+    for priority in 0..3u32 {
+        Process::spawn(async move {
+            Registry::register("pubsub", "topic", priority).unwrap();
+
+            Process::send(current, ());
+
+            let message: Message<String> = Process::receive().await;
+
+            if let Message::User(message) = message {
+                Process::send(current, format!("{}:{}", priority, message));
+            }
+        });
+
+        let _ = Process::receive::<()>().await;
+    }
+
+    assert_eq!(
+        Registry::lookup_entries::<_, _, u32>("pubsub", "topic").len(),
+        3
+    );
+    assert_eq!(
+        Registry::match_entries("pubsub", "topic", |priority: &u32| *priority > 0).len(),
+        2
+    );
+    assert_eq!(
+        Registry::select("pubsub", |key: &RegistryKey, _, priority: &u32| {
+            *key == RegistryKey::from("topic") && *priority == 2
+        })
+        .len(),
+        1
+    );
+
+    assert_eq!(
+        Registry::dispatch("pubsub", "topic", String::from("hello")),
+        3
+    );
+
+    for _ in 0..3 {
+        let message: Message<String> = Process::receive().await;
+
+        assert!(matches!(message, Message::User(message) if message.ends_with(":hello")));
+    }
+
+    Process::sleep(Duration::from_millis(100)).await;
+
+    assert!(Registry::lookup_entries::<_, _, u32>("pubsub", "topic").is_empty());
+}
+
+#[hydra::test]
+async fn registry_duplicate_start_keeps_entries() {
+    Registry::new("duplicate")
+        .with_duplicate_keys()
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    Registry::register("duplicate", "topic", 1u32).unwrap();
+
+    // The second registry fails to start, and is dropped, which must not remove the entries of the first.
+    assert!(Registry::new("duplicate")
+        .with_duplicate_keys()
+        .start(RegistryOptions::new())
+        .await
+        .is_err());
+
+    assert_eq!(
+        Registry::lookup_entries::<_, _, u32>("duplicate", "topic"),
+        vec![(Process::current(), 1)]
+    );
+}
+
+#[hydra::test]
+async fn registry_unregister_demonitors_works() {
+    let registry = Registry::new("unregister")
+        .with_duplicate_keys()
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    let current = Process::current();
+    let monitored = || {
+        Process::info(current)
+            .unwrap()
+            .monitored_by
+            .contains(&registry)
+    };
+
+    Registry::register("unregister", "first", ()).unwrap();
+    Registry::register("unregister", "second", ()).unwrap();
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(monitored());
+
+    Registry::unregister("unregister", "first");
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(monitored());
+
+    Registry::unregister("unregister", "second");
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(!monitored());
+}
+
+#[hydra::test]
+async fn registry_duplicate_start_keeps_ring() {
+    let local: SocketAddr = "127.0.0.1:1600".parse().unwrap();
+
+    Node::start(
+        "local",
+        NodeOptions::new()
+            .listen_address(local)
+            .broadcast_address(local)
+            .transport(MemoryTransport::new()),
+    );
+
+    Registry::new("distributed")
+        .with_distribution()
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    assert_eq!(Registry::owner("distributed", "key"), Some(Node::Local));
+
+    // The second registry fails to start, and is dropped, which must not remove the ring of the first.
+    assert!(Registry::new("distributed")
+        .with_distribution()
+        .start(RegistryOptions::new())
+        .await
+        .is_err());
+
+    assert_eq!(Registry::owner("distributed", "key"), Some(Node::Local));
+
+    Node::stop();
+}
+
+#[hydra::test]
+async fn registry_composite_keys_works() {
+    Registry::new("composite")
+        .with_start(|_| async { Ok(Process::spawn(Process::sleep(Duration::from_secs(60)))) })
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    let key = (7u64, vec![0xDEu8, 0xAD]);
+
+    let pid = Registry::start_process("composite", key.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(Registry::lookup("composite", key).await.unwrap(), Some(pid));
+    assert_eq!(
+        Registry::lookup("composite", (7u64, vec![0xBEu8, 0xEF]))
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[hydra::test]
+async fn registry_eviction_works() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let handler = events.clone();
+
+    Registry::new("sessions")
+        .with_start(|_| async { Ok(Process::spawn(Process::sleep(Duration::from_secs(60)))) })
+        .with_max_entries(1)
+        .with_idle_timeout(Duration::from_millis(100))
+        .with_event_handler(move |event| handler.lock().unwrap().push(event))
+        .start(RegistryOptions::new())
+        .await
+        .unwrap();
+
+    let pid1 = Registry::lookup_or_start("sessions", 1).await.unwrap();
+    let pid2 = Registry::lookup_or_start("sessions", 2).await.unwrap();
+
+    assert_eq!(Registry::lookup("sessions", 1).await.unwrap(), None);
+    assert_eq!(Registry::count("sessions").await.unwrap(), 1);
+
+    Process::sleep(Duration::from_millis(300)).await;
+
+    assert_eq!(Registry::count("sessions").await.unwrap(), 0);
+    assert!(!Process::alive(pid1));
+    assert!(!Process::alive(pid2));
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            RegistryEvent::Registered(1.into(), pid1),
+            RegistryEvent::Evicted(1.into(), pid1),
+            RegistryEvent::Registered(2.into(), pid2),
+            RegistryEvent::Evicted(2.into(), pid2),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Idle timeout must be greater than zero!")]
+fn registry_zero_idle_timeout_panics() {
+    let _ = Registry::new("idle").with_idle_timeout(Duration::ZERO);
+}
@@ -0,0 +1,124 @@
+use std::net::SocketAddr;
+
+use hydra::Node;
+use hydra::RegistryKey;
+use hydra::RegistryRing;
+
+/// Adds the given nodes to the ring.
+fn add_nodes(ring: &RegistryRing, nodes: &[(&str, &str)]) {
+    for (name, address) in nodes {
+        let address: SocketAddr = address.parse().unwrap();
+
+        ring.add_node(
+            &(String::from(*name), address),
+            Node::from((*name, address)),
+        );
+    }
+}
+
+/// Returns the owner of each key from zero up to the given count.
+fn owners(ring: &RegistryRing, count: u64) -> Vec<Node> {
+    (0..count)
+        .map(|key| ring.find_node(&RegistryKey::from(key)).unwrap())
+        .collect()
+}
+
+#[test]
+fn registry_ring_empty_has_no_owner() {
+    let ring = RegistryRing::new();
+
+    assert!(ring.find_node(&RegistryKey::from("user")).is_none());
+}
+
+#[test]
+fn registry_ring_owners_are_pinned() {
+    let ring = RegistryRing::new();
+
+    add_nodes(
+        &ring,
+        &[
+            ("a", "127.0.0.1:1001"),
+            ("b", "127.0.0.1:1002"),
+            ("c", "127.0.0.1:1003"),
+        ],
+    );
+
+    // Every node must agree on these owners, no matter the platform, or compiler version.
+    let pinned = [
+        (RegistryKey::from(0u64), "c"),
+        (RegistryKey::from(1u64), "c"),
+        (RegistryKey::from(2u64), "b"),
+        (RegistryKey::from(3u64), "a"),
+        (RegistryKey::from(-1i32), "b"),
+        (RegistryKey::from("user"), "a"),
+        (RegistryKey::from("order"), "a"),
+        (RegistryKey::Bytes(vec![1, 2, 3]), "b"),
+        (
+            RegistryKey::Tuple(vec![RegistryKey::from("room"), RegistryKey::from(7u32)]),
+            "c",
+        ),
+    ];
+
+    for (key, owner) in pinned {
+        let Some(Node::Remote(name, _)) = ring.find_node(&key) else {
+            panic!("Expected a remote owner!");
+        };
+
+        assert_eq!(name, owner, "{:?}", key);
+    }
+}
+
+#[test]
+fn registry_ring_node_added_only_takes_keys() {
+    let ring = RegistryRing::new();
+
+    add_nodes(&ring, &[("a", "127.0.0.1:1001"), ("b", "127.0.0.1:1002")]);
+
+    let before = owners(&ring, 1000);
+
+    add_nodes(&ring, &[("c", "127.0.0.1:1003")]);
+
+    let after = owners(&ring, 1000);
+
+    let moved = before
+        .iter()
+        .zip(&after)
+        .filter(|(before, after)| before != after)
+        .count();
+
+    assert!(moved > 0);
+
+    for (before, after) in before.iter().zip(&after) {
+        assert!(before == after || *after == ("c", "127.0.0.1:1003".parse().unwrap()));
+    }
+}
+
+#[test]
+fn registry_ring_node_removed_only_gives_keys() {
+    let ring = RegistryRing::new();
+
+    add_nodes(
+        &ring,
+        &[
+            ("a", "127.0.0.1:1001"),
+            ("b", "127.0.0.1:1002"),
+            ("c", "127.0.0.1:1003"),
+        ],
+    );
+
+    let before = owners(&ring, 1000);
+
+    let removed: SocketAddr = "127.0.0.1:1002".parse().unwrap();
+
+    ring.remove_node(&(String::from("b"), removed));
+
+    let after = owners(&ring, 1000);
+
+    for (before, after) in before.iter().zip(&after) {
+        assert!(*after != ("b", removed));
+
+        if *before != ("b", removed) {
+            assert_eq!(before, after);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use hydra::GenServer;
+use hydra::GenServerOptions;
+use hydra::Message;
+use hydra::Process;
+use hydra::ProcessFlags;
+use hydra::SpawnOptions;
+use hydra::Supervisor;
+use hydra::SystemMessage;
+
+#[hydra::test]
+async fn spawn_opt_works() {
+    let current = Process::current();
+
+    let (pid, monitor) = Process::spawn_opt(
+        async move {
+            let info = Process::info(Process::current()).unwrap();
+
+            Process::send(
+                current,
+                (Process::flags() == ProcessFlags::TRAP_EXIT, info.label),
+            );
+        },
+        SpawnOptions::new()
+            .name("spawn_opt")
+            .monitor(true)
+            .flags(ProcessFlags::TRAP_EXIT)
+            .label("worker"),
+    )
+    .unwrap();
+
+    assert!(Process::spawn_opt(async {}, SpawnOptions::new().name("spawn_opt")).is_err());
+
+    let message: Message<(bool, Option<String>)> = Process::receive().await;
+
+    assert!(matches!(message, Message::User((true, Some(label))) if label == "worker"));
+
+    let message: Message<()> = Process::receive().await;
+
+    assert!(matches!(
+        message,
+        Message::System(SystemMessage::ProcessDown(object, reference, _)) if object == pid && Some(reference) == monitor
+    ));
+}
+
+#[hydra::test]
+async fn supervisor_gen_server_start_traps_exits() {
+    let pid = GenServer::start(Supervisor::new(), GenServerOptions::new())
+        .await
+        .unwrap();
+
+    assert!(Process::info(pid).unwrap().trap_exit);
+}
@@ -0,0 +1,284 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use hydra::AsyncStreamOptions;
+use hydra::AsyncTask;
+use hydra::ExitReason;
+use hydra::GenServer;
+use hydra::GenServerOptions;
+use hydra::Local;
+use hydra::Message;
+use hydra::OnTimeout;
+use hydra::Pid;
+use hydra::Process;
+use hydra::RemoteTask;
+use hydra::SystemMessage;
+use hydra::Task;
+use hydra::TaskError;
+use hydra::TaskSupervisor;
+use hydra::TaskSupervisorError;
+use hydra::TaskSupervisorMessage;
+
+#[derive(Serialize, Deserialize)]
+struct Double(u64);
+
+impl RemoteTask for Double {
+    const NAME: &'static str = "double";
+
+    type Output = u64;
+
+    async fn run(self) -> Self::Output {
+        self.0 * 2
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Unknown;
+
+impl RemoteTask for Unknown {
+    const NAME: &'static str = "unknown";
+
+    type Output = ();
+
+    async fn run(self) -> Self::Output {}
+}
+
+#[hydra::test]
+async fn async_task_works() {
+    let task = Task::async_link(async { 1 + 1 });
+
+    assert_eq!(Task::await_async(task, None).await.unwrap(), 2);
+
+    let task: AsyncTask<()> = Task::async_nolink(async {
+        Process::sleep(Duration::from_millis(50)).await;
+
+        panic!("boom");
+    });
+
+    assert!(Task::yield_async(&task, Duration::from_millis(1))
+        .await
+        .is_none());
+
+    assert!(matches!(
+        Task::await_async(task, None).await,
+        Err(TaskError::Exit(ExitReason::Panic(panic))) if panic.message == "boom"
+    ));
+
+    let task = Task::async_link(Process::sleep(Duration::from_secs(60)));
+    let pid = task.pid();
+
+    assert!(matches!(
+        Task::await_async(task, Some(Duration::from_millis(10))).await,
+        Err(TaskError::Timeout)
+    ));
+    assert!(!Process::alive(pid));
+
+    let task = Task::async_nolink(Process::sleep(Duration::from_secs(60)));
+
+    assert!(Task::shutdown_async(task).await.is_none());
+}
+
+#[hydra::test]
+async fn task_supervisor_works() {
+    let supervisor = TaskSupervisor::new()
+        .remote_task::<Double>()
+        .start(GenServerOptions::new())
+        .await
+        .unwrap();
+
+    let task = TaskSupervisor::async_nolink(supervisor, async { String::from("hello") })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        TaskSupervisor::children(supervisor).await.unwrap(),
+        vec![task.pid()]
+    );
+    assert_eq!(Task::await_async(task, None).await.unwrap(), "hello");
+
+    let task = TaskSupervisor::async_link_remote(supervisor, Double(21))
+        .await
+        .unwrap();
+
+    assert_eq!(Task::await_async(task, None).await.unwrap(), 42);
+
+    assert!(matches!(
+        TaskSupervisor::start_child_remote(supervisor, Unknown).await,
+        Err(TaskSupervisorError::UnknownTask(_))
+    ));
+
+    let pid = TaskSupervisor::start_child(supervisor, async {})
+        .await
+        .unwrap();
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(!TaskSupervisor::children(supervisor)
+        .await
+        .unwrap()
+        .contains(&pid));
+}
+
+#[hydra::test]
+async fn task_supervisor_owner_down_works() {
+    let supervisor = TaskSupervisor::new()
+        .start(GenServerOptions::new())
+        .await
+        .unwrap();
+
+    let owner = Process::spawn(Process::sleep(Duration::from_secs(60)));
+
+    // The owner goes down after the task was started, but before it signals the task to run.
+    let message = TaskSupervisorMessage::Start(
+        Local::new(Box::pin(async { panic!("The task must not run!") })),
+        Some(owner),
+    );
+
+    let Ok(TaskSupervisorMessage::StartSuccess(pid)) =
+        TaskSupervisor::call(supervisor, message, None).await
+    else {
+        panic!("Expected the task to start!");
+    };
+
+    let monitor = Process::monitor(pid);
+
+    Process::exit(owner, ExitReason::Kill);
+
+    let Message::System(SystemMessage::ProcessDown(_, reference, exit_reason)) =
+        Process::receive::<()>().await
+    else {
+        panic!("Expected a process down message!");
+    };
+
+    assert_eq!(reference, monitor);
+    assert_eq!(exit_reason, ExitReason::Normal);
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(TaskSupervisor::children(supervisor)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[hydra::test]
+async fn async_stream_works() {
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    let results: Vec<u64> = Task::async_stream(
+        [30, 10, 20, 0],
+        |delay| async move {
+            let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+
+            PEAK.fetch_max(running, Ordering::SeqCst);
+
+            Process::sleep(Duration::from_millis(delay)).await;
+
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+
+            delay
+        },
+        AsyncStreamOptions::new().max_concurrency(2),
+    )
+    .map(Result::unwrap)
+    .collect()
+    .await;
+
+    assert_eq!(results, vec![30, 10, 20, 0]);
+    assert_eq!(PEAK.load(Ordering::SeqCst), 2);
+
+    let results: Vec<Option<u64>> = Task::async_stream(
+        [1000, 0],
+        |delay| async move {
+            Process::sleep(Duration::from_millis(delay)).await;
+
+            delay
+        },
+        AsyncStreamOptions::new()
+            .max_concurrency(2)
+            .ordered(false)
+            .timeout(Duration::from_millis(50))
+            .on_timeout(OnTimeout::KillTask),
+    )
+    .map(Result::ok)
+    .collect()
+    .await;
+
+    assert_eq!(results, vec![Some(0), None]);
+
+    let (_, monitor) = Process::spawn_monitor(async {
+        let _ = Task::async_stream(
+            [1000],
+            |delay| Process::sleep(Duration::from_millis(delay)),
+            AsyncStreamOptions::new().timeout(Duration::from_millis(10)),
+        )
+        .collect::<Vec<_>>()
+        .await;
+    });
+
+    let message: Message<()> = Process::receive().await;
+
+    assert!(matches!(
+        message,
+        Message::System(SystemMessage::ProcessDown(_, tag, reason)) if tag == monitor && reason == "timeout"
+    ));
+}
+
+#[hydra::test]
+async fn async_stream_drop_in_other_process_works() {
+    let current = Process::current();
+
+    let mut stream = Box::pin(Task::async_stream(
+        [0, 1000],
+        move |delay| async move {
+            Process::send(current, (delay, Process::current()));
+
+            Process::sleep(Duration::from_millis(delay)).await;
+
+            delay
+        },
+        AsyncStreamOptions::new().max_concurrency(2).ordered(false),
+    ));
+
+    assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+
+    let slow = Process::receiver()
+        .for_message::<(u64, Pid)>()
+        .select(|message| matches!(message, Message::User((1000, _))))
+        .await;
+
+    let Message::User((_, slow)) = slow else {
+        panic!("Expected the slow task!");
+    };
+
+    assert!(Process::alive(slow));
+
+    let (_, monitor) = Process::spawn_monitor(async move {
+        drop(stream);
+    });
+
+    let _ = Process::receiver()
+        .select(|message| {
+            matches!(message, Message::System(SystemMessage::ProcessDown(_, tag, _)) if *tag == monitor)
+        })
+        .await;
+
+    Process::sleep(Duration::from_millis(10)).await;
+
+    assert!(!Process::alive(slow));
+
+    let down = Process::receiver().select(|message| {
+        matches!(message, Message::System(SystemMessage::ProcessDown(dest, _, _)) if *dest == slow)
+    });
+
+    assert!(Process::timeout(Duration::from_millis(10), down)
+        .await
+        .is_err());
+}
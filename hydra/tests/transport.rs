@@ -0,0 +1,114 @@
+use std::net::SocketAddr;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use futures_util::SinkExt;
+use futures_util::StreamExt;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+
+use tokio_util::codec::Framed;
+
+use hydra::frame::Codec;
+use hydra::frame::Frame;
+use hydra::frame::Hello;
+use hydra::frame::Ping;
+use hydra::frame::Send;
+
+use hydra::MemoryTransport;
+use hydra::MessageFormat;
+use hydra::NodeStats;
+use hydra::NodeTransport;
+use hydra::TcpTransport;
+
+/// Sends frames in both directions over a connection of the given transport, and checks they arrive intact.
+async fn codec_round_trip<T: NodeTransport>(transport: T, address: SocketAddr) {
+    let mut listener = transport.listen(address).await.unwrap();
+    let client = transport.connect(address).await.unwrap();
+    let server = listener.accept().await.unwrap();
+
+    let mut client = Framed::new(client, Codec::new(Arc::new(NodeStats::new()), 1024));
+    let mut server = Framed::new(server, Codec::new(Arc::new(NodeStats::new()), 1024));
+
+    client
+        .send(Hello::new(String::from("client"), address, false).into())
+        .await
+        .unwrap();
+
+    let Some(Ok(Frame::Hello(mut hello))) = server.next().await else {
+        panic!("Expected a hello frame!");
+    };
+
+    assert_eq!(hello.name, "client");
+    assert_eq!(hello.broadcast_address, address);
+    assert!(hello.validate());
+
+    let message: Arc<[u8]> = Arc::from(vec![7u8; 512]);
+    let send = Send::with_pid(
+        NonZeroU64::new(1).unwrap(),
+        MessageFormat::MessagePack,
+        message.clone(),
+    );
+
+    server.send(Ping::new(1337).into()).await.unwrap();
+    server.send(send.into()).await.unwrap();
+
+    let Some(Ok(Frame::Ping(ping))) = client.next().await else {
+        panic!("Expected a ping frame!");
+    };
+
+    assert_eq!(ping.timestamp, 1337);
+
+    let Some(Ok(Frame::Send(send))) = client.next().await else {
+        panic!("Expected a send frame!");
+    };
+
+    assert_eq!(send.message, message);
+}
+
+#[hydra::test]
+async fn memory_transport_works() {
+    let address: SocketAddr = "127.0.0.1:1337".parse().unwrap();
+    let transport = MemoryTransport::new();
+
+    let mut listener = transport.listen(address).await.unwrap();
+    let mut client = transport.connect(address).await.unwrap();
+    let mut server = listener.accept().await.unwrap();
+
+    client.write_all(b"hello").await.unwrap();
+
+    let mut buffer = [0; 5];
+
+    server.read_exact(&mut buffer).await.unwrap();
+
+    assert_eq!(&buffer, b"hello");
+}
+
+#[hydra::test]
+async fn memory_transport_refuses_unknown_address() {
+    let address: SocketAddr = "127.0.0.1:1338".parse().unwrap();
+
+    assert!(MemoryTransport::new().connect(address).await.is_err());
+}
+
+#[hydra::test]
+async fn memory_transport_codec_works() {
+    codec_round_trip(MemoryTransport::new(), "127.0.0.1:1339".parse().unwrap()).await;
+}
+
+#[hydra::test]
+async fn tcp_transport_codec_works() {
+    codec_round_trip(TcpTransport::new(), "127.0.0.1:41339".parse().unwrap()).await;
+}
+
+#[cfg(unix)]
+#[hydra::test]
+async fn unix_transport_codec_works() {
+    let directory = std::env::temp_dir();
+    let transport = hydra::UnixTransport::new(move |address| {
+        directory.join(format!("hydra-transport-{}.sock", address.port()))
+    });
+
+    codec_round_trip(transport, "127.0.0.1:1340".parse().unwrap()).await;
+}